@@ -0,0 +1,52 @@
+//! Benchmarks the typed graph traversal in `field_index` against a synthetic crate of
+//! thousands of entities, to lock in the win over the old serde_json round-trip it
+//! replaced (see `ReadCommand::Fields`/`ReadCommand::Value` in `src/main.rs`).
+
+#[path = "../src/field_index.rs"]
+mod field_index;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rocraters::ro_crate::constraints::DataType;
+use rocraters::ro_crate::data_entity::DataEntity;
+use rocraters::ro_crate::graph_vector::GraphVector;
+use rocraters::ro_crate::modify::DynamicEntity;
+use std::collections::HashMap;
+
+const ENTITY_COUNT: usize = 5_000;
+
+fn synthetic_graph() -> Vec<GraphVector> {
+    (0..ENTITY_COUNT)
+        .map(|i| {
+            let mut dynamic_entity = HashMap::new();
+            dynamic_entity.insert(
+                "license".to_string(),
+                DynamicEntity::EntityString(if i % 2 == 0 { "MIT" } else { "Apache-2.0" }.into()),
+            );
+            dynamic_entity.insert(
+                "name".to_string(),
+                DynamicEntity::EntityString(format!("entity-{i}")),
+            );
+
+            GraphVector::DataEntity(DataEntity {
+                id: format!("#entity-{i}"),
+                type_: DataType::Term("File".to_string()),
+                dynamic_entity: Some(dynamic_entity),
+            })
+        })
+        .collect()
+}
+
+fn bench_field_lookup(c: &mut Criterion) {
+    let graph = synthetic_graph();
+
+    c.bench_function("collect_graph_field_values/license", |b| {
+        b.iter(|| field_index::collect_graph_field_values(&graph, "license"))
+    });
+
+    c.bench_function("search_graph_values/miss", |b| {
+        b.iter(|| field_index::search_graph_values(&graph, "no-such-value", false))
+    });
+}
+
+criterion_group!(benches, bench_field_lookup);
+criterion_main!(benches);
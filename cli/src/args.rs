@@ -20,6 +20,8 @@ pub enum CrateAction {
     Add(AddCommand),
     /// Delete an entity in an Ro-Crate
     Delete(DeleteCommand),
+    /// Import one or more entities from a JSON or YAML batch file, non-interactively
+    Import(ImportCommand),
     /// Modify a particular entity within an Ro-Crate (includes Root and Descriptor)
     #[clap(subcommand)]
     Modify(ModifyCommand),
@@ -130,6 +132,21 @@ pub struct DeleteCommand {
     pub recursive: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct ImportCommand {
+    /// Target crate
+    #[clap(
+        short,
+        long,
+        required = false,
+        default_value = "ro-crate-metadata.json"
+    )]
+    pub target_crate: String,
+    /// Path to a batch file (`.json`, `.yaml` or `.yml`) describing one or more entities,
+    /// each with an `@id`, one or more `@type` terms, and a map of typed properties
+    pub batch_file: String,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum ModifyCommand {
     /// Add a ID to an entity
@@ -219,6 +236,77 @@ pub struct ZipCrateCommand {
     // Flatten contents to remove folder stucture in zip
     #[clap(short, long, default_value_t = false)]
     pub flatten: bool,
+    // Synthesise minimal data entities for any file or directory not already described,
+    // and add them to the root Dataset's hasPart
+    #[clap(short = 'c', long, default_value_t = false)]
+    pub create_entities: bool,
+    /// Compression method to use for zip archives
+    #[clap(short = 'z', long, default_value_t = CompressionMethodArg::Deflated)]
+    pub compression: CompressionMethodArg,
+    /// Compression level, meaning depends on the chosen method; omit for the method's default
+    #[clap(short = 'l', long)]
+    pub compression_level: Option<i64>,
+    /// Preserve unix file permissions in the archive
+    #[clap(short = 'p', long, default_value_t = true)]
+    pub preserve_permissions: bool,
+    /// Follow symlinks while walking the crate directory, with cycle detection
+    #[clap(short = 's', long, default_value_t = false)]
+    pub follow_symlinks: bool,
+    /// Fingerprint each packaged file's contentSize/sha256 onto its data entity, and add a
+    /// manifest-sha256.txt entry to the archive
+    #[clap(short = 'k', long, default_value_t = false)]
+    pub checksums: bool,
+    /// Also compute a sha512 digest alongside sha256; ignored unless --checksums is set
+    #[clap(long, default_value_t = false)]
+    pub sha512: bool,
+    /// Also compute a BLAKE3 digest alongside sha256; ignored unless --checksums is set
+    #[clap(long, default_value_t = false)]
+    pub blake3: bool,
+    /// Download any http(s) `@id`s referenced in the crate and repack them under `remote/`,
+    /// rewriting their `@id` so the archive is self-contained
+    #[clap(long, default_value_t = false)]
+    pub embed_remote: bool,
+    /// Number of worker threads used to read and hash packaged files concurrently (requires
+    /// the `rayon` feature); 0 lets the thread pool pick its own default
+    #[clap(long, default_value_t = 0)]
+    pub threads: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CompressionMethodArg {
+    Stored,
+    Deflated,
+    Bzip2,
+    Zstd,
+}
+
+impl FromStr for CompressionMethodArg {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_ascii_lowercase().as_str() {
+            "stored" => Ok(CompressionMethodArg::Stored),
+            "deflated" => Ok(CompressionMethodArg::Deflated),
+            "bzip2" => Ok(CompressionMethodArg::Bzip2),
+            "zstd" => Ok(CompressionMethodArg::Zstd),
+            _ => Err("invalid compression method, expected `stored`, `deflated`, `bzip2` or `zstd`"),
+        }
+    }
+}
+
+impl std::fmt::Display for CompressionMethodArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CompressionMethodArg::Stored => "stored",
+                CompressionMethodArg::Deflated => "deflated",
+                CompressionMethodArg::Bzip2 => "bzip2",
+                CompressionMethodArg::Zstd => "zstd",
+            }
+        )
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -231,6 +319,10 @@ pub enum ReadCommand {
     Fields(ReadFieldsCommand),
     /// Read entity containing specific value
     Value(ReadValueCommand),
+    /// Fuzzy full-text search over every entity's string values
+    Search(ReadSearchCommand),
+    /// Select entities by type/field/reference selector, e.g. `type:Dataset&field:license=MIT`
+    Query(ReadQueryCommand),
 }
 
 #[derive(Debug, Args)]
@@ -303,6 +395,43 @@ pub struct ReadValueCommand {
     pub location: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct ReadSearchCommand {
+    /// Target crate
+    #[clap(
+        short,
+        long,
+        required = false,
+        default_value = "ro-crate-metadata.json"
+    )]
+    pub target_crate: String,
+    /// Term to fuzzy search for across every entity's tokenized string values
+    pub query: String,
+    /// Maximum Levenshtein edit distance allowed between a token and the query (1 or 2)
+    #[clap(short, long, default_value_t = 1)]
+    pub distance: u32,
+}
+
+#[derive(Debug, Args)]
+pub struct ReadQueryCommand {
+    /// Target crate
+    #[clap(
+        short,
+        long,
+        required = false,
+        default_value = "ro-crate-metadata.json"
+    )]
+    pub target_crate: String,
+    /// Selector, e.g. `type:Dataset`, `field:license=MIT`, `ref:<id>`, combinable with `&`
+    pub selector: String,
+    /// Also inline each matched entity's directly referenced entities (one hop)
+    #[clap(short, long)]
+    pub expand: bool,
+    /// Prints full view without trimming
+    #[clap(short, long)]
+    pub fit: bool,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum PackageCommand {
     /// Zip full crate
@@ -325,4 +454,27 @@ pub struct ValidateCrateCommand {
         default_value = "ro-crate-metadata.json"
     )]
     pub target_crate: String,
+    /// Output format for the diagnostic report
+    #[clap(short, long, default_value = "table")]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone)]
+pub enum OutputFormat {
+    /// Human-readable table
+    Table,
+    /// Machine-readable JSON array of diagnostics, for CI and editor problem matchers
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err("invalid output format, expected `table` or `json`"),
+        }
+    }
 }
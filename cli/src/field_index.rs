@@ -0,0 +1,192 @@
+//! Typed (non-JSON) traversal helpers for `ReadCommand::Fields` and `ReadCommand::Value`.
+//!
+//! Both commands used to serialize the whole crate to `serde_json::Value` and walk it
+//! recursively on every invocation. These operate directly on `Vec<GraphVector>`
+//! instead: each variant's `@id` and `dynamic_entity` map are read without cloning into
+//! JSON, so a lookup walks only each entity's own values and never builds a serialized
+//! copy of the whole crate.
+
+use crate::{graph_vector_dynamic_entity, graph_vector_type};
+use rocraters::ro_crate::graph_vector::GraphVector;
+use rocraters::ro_crate::modify::DynamicEntity;
+use std::collections::HashMap;
+
+/// Renders a `DynamicEntity` the way the old JSON-based walk did: the plain string
+/// form for strings, `Debug` for everything else.
+pub fn dynamic_entity_to_string(value: &DynamicEntity) -> String {
+    match value {
+        DynamicEntity::EntityString(s) => s.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Walks into `value`'s nested containers (`EntityObject`, `EntityVecObject`, `EntityVec`,
+/// `NestedDynamicEntity`), pushing any `field_name` match found at any depth onto `out`.
+/// Mirrors the depth-first attribution the pre-refactor JSON-based walk used to do.
+fn collect_nested_field_matches<'a>(
+    value: &'a DynamicEntity,
+    field_name: &str,
+    out: &mut Vec<&'a DynamicEntity>,
+) {
+    match value {
+        DynamicEntity::EntityObject(map) => {
+            if let Some(v) = map.get(field_name) {
+                out.push(v);
+            }
+            for v in map.values() {
+                collect_nested_field_matches(v, field_name, out);
+            }
+        }
+        DynamicEntity::EntityVecObject(vec) => {
+            for map in vec {
+                if let Some(v) = map.get(field_name) {
+                    out.push(v);
+                }
+                for v in map.values() {
+                    collect_nested_field_matches(v, field_name, out);
+                }
+            }
+        }
+        DynamicEntity::EntityVec(vec) => {
+            for v in vec {
+                collect_nested_field_matches(v, field_name, out);
+            }
+        }
+        DynamicEntity::NestedDynamicEntity(boxed) => {
+            collect_nested_field_matches(boxed, field_name, out);
+        }
+        _ => {}
+    }
+}
+
+/// Collects every `(@id, value, count)` where `field_name` matches, scanning the typed
+/// graph directly instead of a serialized JSON copy. `@id`/`@type` are handled as
+/// special cases; everything else is looked up directly in the `dynamic_entity` map and
+/// then, like the pre-refactor recursive JSON walk, at any nesting depth beneath it.
+pub fn collect_graph_field_values(
+    graph: &[GraphVector],
+    field_name: &str,
+) -> Vec<(String, String, isize)> {
+    let mut collected_values: HashMap<(String, String), isize> = HashMap::new();
+
+    for graph_vector in graph {
+        let id = graph_vector.get_id().clone();
+
+        if field_name == "@id" {
+            *collected_values.entry((id.clone(), id)).or_insert(0) += 1;
+            continue;
+        }
+        if field_name == "@type" {
+            let value = format!("{:?}", graph_vector_type(graph_vector));
+            *collected_values.entry((id, value)).or_insert(0) += 1;
+            continue;
+        }
+
+        let Some(fields) = graph_vector_dynamic_entity(graph_vector) else {
+            continue;
+        };
+
+        if let Some(value) = fields.get(field_name) {
+            let value_str = dynamic_entity_to_string(value);
+            *collected_values
+                .entry((id.clone(), value_str))
+                .or_insert(0) += 1;
+        }
+
+        let mut nested_matches = Vec::new();
+        for value in fields.values() {
+            collect_nested_field_matches(value, field_name, &mut nested_matches);
+        }
+        for value in nested_matches {
+            let value_str = dynamic_entity_to_string(value);
+            *collected_values
+                .entry((id.clone(), value_str))
+                .or_insert(0) += 1;
+        }
+    }
+
+    collected_values
+        .into_iter()
+        .map(|((id, value), count)| (id, value, count))
+        .collect()
+}
+
+/// Counts how many values nested in `value` (including `value` itself) render to
+/// `search_value`, descending into `EntityObject`, `EntityVecObject`, `EntityVec` and
+/// `NestedDynamicEntity` the way the pre-refactor recursive JSON walk did.
+fn count_nested_value_matches(value: &DynamicEntity, search_value: &str) -> usize {
+    let mut matches = if dynamic_entity_to_string(value) == search_value {
+        1
+    } else {
+        0
+    };
+
+    match value {
+        DynamicEntity::EntityObject(map) => {
+            for v in map.values() {
+                matches += count_nested_value_matches(v, search_value);
+            }
+        }
+        DynamicEntity::EntityVecObject(vec) => {
+            for map in vec {
+                for v in map.values() {
+                    matches += count_nested_value_matches(v, search_value);
+                }
+            }
+        }
+        DynamicEntity::EntityVec(vec) => {
+            for v in vec {
+                matches += count_nested_value_matches(v, search_value);
+            }
+        }
+        DynamicEntity::NestedDynamicEntity(boxed) => {
+            matches += count_nested_value_matches(boxed, search_value);
+        }
+        _ => {}
+    }
+
+    matches
+}
+
+/// Finds every entity whose `@id` or dynamic property value exactly matches
+/// `search_value`, scanning the typed graph directly instead of a serialized copy.
+/// Property values are matched at any nesting depth, not just the top-level map.
+pub fn search_graph_values(
+    graph: &[GraphVector],
+    search_value: &str,
+    location: bool,
+) -> Vec<(String, String, isize)> {
+    let mut occurrences: HashMap<(String, String), isize> = HashMap::new();
+
+    for graph_vector in graph {
+        let id = graph_vector.get_id().clone();
+
+        if id == search_value {
+            *occurrences
+                .entry((id.clone(), search_value.to_string()))
+                .or_insert(0) += 1;
+        }
+
+        if let Some(fields) = graph_vector_dynamic_entity(graph_vector) {
+            let matches: usize = fields
+                .values()
+                .map(|value| count_nested_value_matches(value, search_value))
+                .sum();
+
+            if matches > 0 {
+                *occurrences
+                    .entry((id.clone(), search_value.to_string()))
+                    .or_insert(0) += matches as isize;
+
+                if location {
+                    println!("Found in object:\n{:#?}\n", graph_vector);
+                }
+            }
+        }
+    }
+
+    occurrences
+        .into_iter()
+        .map(|((id, value), count)| (id, value, count))
+        .collect()
+}
@@ -1,27 +1,33 @@
 //! Cli binding logic
 
-use ::serde::Serialize;
+use ::serde::{Deserialize, Serialize};
 use args::{
-    AddCommand, ContextType, CrateAction, DeleteCommand, ModifyCommand, PackageCommand,
-    ReadCommand, ValidateCommand,
+    AddCommand, ContextType, CrateAction, DeleteCommand, ModifyCommand, OutputFormat,
+    PackageCommand, ReadCommand, ValidateCommand,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use clap::Parser;
 use constraints::{DataType, EntityValue, Id, License};
 use data_entity::DataEntity;
+use fst::automaton::Levenshtein;
+use fst::{IntoStreamer, Map as FstMap, MapBuilder, Streamer};
 use json_to_table::json_to_table;
 use read::{crate_path, read_crate};
 use rocraters::ro_crate::graph_vector::GraphVector;
+use rocraters::ro_crate::modify::DynamicEntity;
 use rocraters::ro_crate::rocrate::{ContextItem, RoCrate, RoCrateContext};
 use rocraters::ro_crate::{constraints, data_entity, metadata_descriptor, read, root, write};
 use serde_json::Value as JsonValue;
 use serde_json::{json, to_string_pretty};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::{self, Write};
 use std::path::PathBuf;
 use tabled::settings::{object::Rows, Style, Width};
-use write::{write_crate, zip_crate};
+use write::{
+    write_crate, zip_crate, ArchiveFormat, ChecksumOptions, CompressionMethod, CompressionOptions,
+};
 pub mod args;
+pub mod field_index;
 
 fn main() {
     let args = args::RoCrateArgs::parse();
@@ -62,6 +68,17 @@ fn main() {
 
             write_crate(&rocrate, delete_command.target_crate)
         }
+        CrateAction::Import(import_command) => {
+            let mut rocrate = open_and_load_crate(&import_command.target_crate);
+
+            match read_batch_file(&import_command.batch_file) {
+                Ok(entities) => {
+                    run_batch_import(&mut rocrate, entities);
+                    write_crate(&rocrate, import_command.target_crate)
+                }
+                Err(e) => eprintln!("Failed to read batch file: {}", e),
+            }
+        }
         CrateAction::Modify(modify_command) => match modify_command {
             ModifyCommand::AddIdValue(add_id_value_command) => {
                 let mut rocrate = open_and_load_crate(&add_id_value_command.target_crate);
@@ -176,8 +193,10 @@ fn main() {
             }
             ReadCommand::Fields(read_fields_command) => {
                 let rocrate = open_and_load_crate(&read_fields_command.target_crate);
-                let values =
-                    get_field_values_with_count(&rocrate.graph, &read_fields_command.field);
+                let values = field_index::collect_graph_field_values(
+                    &rocrate.graph,
+                    &read_fields_command.field,
+                );
                 print_as_table(
                     values,
                     "@id",
@@ -187,13 +206,48 @@ fn main() {
             }
             ReadCommand::Value(read_value_command) => {
                 let rocrate = open_and_load_crate(&read_value_command.target_crate);
-                let values = search_and_print_struct(
+                let values = field_index::search_graph_values(
                     &rocrate.graph,
                     &read_value_command.value,
                     read_value_command.location,
                 );
                 print_as_table(values, "Object ID", "Value", "Count");
             }
+            ReadCommand::Search(read_search_command) => {
+                let rocrate = open_and_load_crate(&read_search_command.target_crate);
+                match build_token_index(&rocrate.graph) {
+                    Ok(index) => {
+                        let values = search_index(
+                            &index,
+                            &read_search_command.query,
+                            read_search_command.distance,
+                        );
+                        print_as_table(values, "Object ID", "Matched Tokens", "Frequency");
+                    }
+                    Err(e) => eprintln!("Failed to build search index: {}", e),
+                }
+            }
+            ReadCommand::Query(read_query_command) => {
+                let rocrate = open_and_load_crate(&read_query_command.target_crate);
+                match parse_query(&read_query_command.selector) {
+                    Ok(predicates) => {
+                        let mut matched = evaluate_query(&rocrate, &predicates);
+                        if read_query_command.expand {
+                            matched = expand_query_results(&rocrate, matched);
+                        }
+
+                        let mut table = json_to_table(&json!(matched)).into_table();
+                        table.with(Style::modern_rounded());
+                        if read_query_command.fit {
+                            table.modify(Rows::new(1..), Width::truncate(200).suffix("..."));
+                        } else {
+                            table.modify(Rows::new(1..), Width::truncate(79).suffix("..."));
+                        }
+                        println!("{}", table)
+                    }
+                    Err(e) => eprintln!("Invalid query selector: {}", e),
+                }
+            }
         },
         CrateAction::Package(package_command) => match package_command {
             PackageCommand::Zip(zip_command) => {
@@ -204,14 +258,52 @@ fn main() {
                     path = crate_path(zip_command.target_crate.as_str());
                 }
                 println!("{:?}", path);
-                let _ = zip_crate(&path, true, 1);
+                let compression = CompressionOptions {
+                    method: match zip_command.compression {
+                        args::CompressionMethodArg::Stored => CompressionMethod::Stored,
+                        args::CompressionMethodArg::Deflated => CompressionMethod::Deflated,
+                        args::CompressionMethodArg::Bzip2 => CompressionMethod::Bzip2,
+                        args::CompressionMethodArg::Zstd => CompressionMethod::Zstd,
+                    },
+                    level: zip_command.compression_level,
+                    preserve_permissions: zip_command.preserve_permissions,
+                };
+                let _ = zip_crate(
+                    &path,
+                    true,
+                    1,
+                    false,
+                    false,
+                    None,
+                    false,
+                    None,
+                    ArchiveFormat::Zip,
+                    zip_command.create_entities,
+                    compression,
+                    zip_command.follow_symlinks,
+                    ChecksumOptions {
+                        enabled: zip_command.checksums,
+                        sha512: zip_command.sha512,
+                        blake3: zip_command.blake3,
+                    },
+                    zip_command.embed_remote,
+                    zip_command.threads,
+                );
             }
         },
         CrateAction::Validate(validate_command) => match validate_command {
             ValidateCommand::Basic(basic) => {
                 let crate_name = crate_path(&basic.target_crate);
                 match read_crate(&crate_name, 2) {
-                    Ok(rocrate) => println!("Crate Valid"),
+                    Ok(rocrate) => {
+                        let diagnostics = run_validation(&rocrate);
+                        match basic.format {
+                            OutputFormat::Json => {
+                                println!("{}", to_string_pretty(&diagnostics).unwrap())
+                            }
+                            OutputFormat::Table => print_diagnostics(&diagnostics),
+                        }
+                    }
                     Err(e) => println!("Crate not valid: {:?}", e),
                 }
             }
@@ -363,6 +455,117 @@ fn add_entity(mut rocrate: RoCrate, input: &AddCommand) -> RoCrate {
     rocrate
 }
 
+/// One entity from a batch import file: an `@id`, one or more `@type` terms, and a map
+/// of typed properties mirroring the value kinds offered by `prompt_for_types`.
+#[derive(Debug, Deserialize)]
+struct BatchEntity {
+    id: String,
+    #[serde(rename = "type")]
+    type_: BatchDataType,
+    #[serde(default)]
+    properties: HashMap<String, BatchPropertyValue>,
+}
+
+/// A single `@type` term, or a list of them - accepted either way in a batch file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BatchDataType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl From<BatchDataType> for DataType {
+    fn from(value: BatchDataType) -> Self {
+        match value {
+            BatchDataType::Single(term) => DataType::Term(term),
+            BatchDataType::Multiple(terms) => DataType::TermArray(terms),
+        }
+    }
+}
+
+/// A typed property value in a batch file, tagged the same way `prompt_for_types` asks
+/// for a type interactively: string, id, a list of ids, integer, float, or bool.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BatchPropertyValue {
+    String { value: String },
+    Id { value: String },
+    IdArray { value: Vec<String> },
+    Integer { value: i64 },
+    Float { value: f64 },
+    Bool { value: bool },
+}
+
+impl From<BatchPropertyValue> for EntityValue {
+    fn from(value: BatchPropertyValue) -> Self {
+        match value {
+            BatchPropertyValue::String { value } => EntityValue::EntityString(value),
+            BatchPropertyValue::Id { value } => EntityValue::EntityId(Id::Id(value)),
+            BatchPropertyValue::IdArray { value } => EntityValue::EntityId(Id::IdArray(value)),
+            BatchPropertyValue::Integer { value } => EntityValue::Entityi64(value),
+            BatchPropertyValue::Float { value } => EntityValue::Entityf64(value),
+            BatchPropertyValue::Bool { value } => EntityValue::EntityBool(Some(value)),
+        }
+    }
+}
+
+/// Reads and parses a batch import file, choosing JSON or YAML by extension.
+fn read_batch_file(path: &str) -> Result<Vec<BatchEntity>, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        serde_yaml::from_str(&contents).map_err(|e| format!("invalid YAML in {}: {}", path, e))
+    } else {
+        serde_json::from_str(&contents).map_err(|e| format!("invalid JSON in {}: {}", path, e))
+    }
+}
+
+/// Applies every entity from a batch file in one pass: new `@id`s are created via
+/// `add_entity`-style construction, existing ones have their properties merged in via
+/// `add_dynamic_entity_property`. Each entity's errors are reported without aborting the
+/// rest of the run.
+fn run_batch_import(rocrate: &mut RoCrate, entities: Vec<BatchEntity>) {
+    for entity in entities {
+        let id = entity.id.clone();
+        match apply_batch_entity(rocrate, entity) {
+            Ok(()) => println!("Imported {}", id),
+            Err(e) => eprintln!("Failed to import {}: {}", id, e),
+        }
+    }
+}
+
+fn apply_batch_entity(rocrate: &mut RoCrate, entity: BatchEntity) -> Result<(), String> {
+    let properties: HashMap<String, EntityValue> = entity
+        .properties
+        .into_iter()
+        .map(|(key, value)| (key, EntityValue::from(value)))
+        .collect();
+
+    if rocrate.find_entity_index(&entity.id).is_some() {
+        if properties.is_empty() {
+            return Ok(());
+        }
+        if rocrate.add_dynamic_entity_property(&entity.id, properties) {
+            Ok(())
+        } else {
+            Err(format!("entity {} disappeared mid-batch", entity.id))
+        }
+    } else {
+        let data_entity = DataEntity {
+            id: entity.id,
+            type_: entity.type_.into(),
+            dynamic_entity: if properties.is_empty() {
+                None
+            } else {
+                Some(properties)
+            },
+        };
+        rocrate.graph.push(GraphVector::DataEntity(data_entity));
+        Ok(())
+    }
+}
+
 /// Adds a dynamic entity to a entity that's in the process of being made
 fn add_dynamic_entity() -> Option<HashMap<String, EntityValue>> {
     let mut dynamic_entity: HashMap<String, EntityValue> = HashMap::new();
@@ -497,56 +700,6 @@ fn delete_entity(mut rocrate: RoCrate, input: &DeleteCommand) -> RoCrate {
     rocrate
 }
 
-/// NOTE: This is massively suboptimal but it's a very quick and easy way to just get the values
-/// without having to spend the effort to think of how to parse it all agian
-fn get_field_values_with_count<T: Serialize>(
-    object: &T,
-    field_name: &str,
-) -> Vec<(String, String, isize)> {
-    let mut collected_values = HashMap::new();
-    let json = serde_json::to_value(object).unwrap();
-    collect_field_values_recursive(&json, field_name, &mut collected_values);
-
-    collected_values
-        .into_iter()
-        .map(|((id, value), count)| (id, value, count))
-        .collect()
-}
-
-/// Collects field values recursively, now including "@id" for each match.
-fn collect_field_values_recursive(
-    json: &JsonValue,
-    field_name: &str,
-    collected_values: &mut HashMap<(String, String), isize>,
-) {
-    match json {
-        JsonValue::Object(obj) => {
-            // Check if the object contains "@id"
-            let current_id = obj
-                .get("@id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            for (key, value) in obj {
-                if key == field_name {
-                    let value_str = value.to_string();
-                    let key = (current_id.clone(), value_str);
-                    *collected_values.entry(key).or_insert(0) += 1;
-                }
-                // Continue recursive search within the object
-                collect_field_values_recursive(value, field_name, collected_values);
-            }
-        }
-        JsonValue::Array(arr) => {
-            for item in arr {
-                collect_field_values_recursive(item, field_name, collected_values);
-            }
-        }
-        _ => {}
-    }
-}
-
 /// For fun
 fn print_as_table(
     data: Vec<(String, String, isize)>,
@@ -588,72 +741,445 @@ fn print_as_table(
     }
 }
 
-fn search_and_print_struct<T: Serialize>(
-    object: &T,
-    search_value: &str,
-    location: bool,
-) -> Vec<(String, String, isize)> {
-    let json = serde_json::to_value(object).unwrap();
-    let mut occurrences = HashMap::new();
-    search_and_print_recursive(&json, search_value, &mut occurrences, location);
+/// An in-memory inverted index over every string/`EntityId` value in the crate graph,
+/// supporting fuzzy lookup via a Levenshtein automaton intersected with an `fst::Map`.
+///
+/// The `fst::Map` only stores `token -> postings index`, since `fst` values are limited
+/// to a single `u64`; the actual posting lists (owning `@id`s plus occurrence counts)
+/// live alongside it in `postings`.
+struct TokenIndex {
+    map: FstMap<Vec<u8>>,
+    postings: Vec<Vec<(String, u64)>>,
+}
 
-    // Convert occurrences to a vector of tuples for printing
-    occurrences
-        .into_iter()
-        .map(|((id, value), count)| (id, value, count))
-        .collect()
+/// Walks every entity in `graph`, tokenizing all string values on whitespace and
+/// case-folding each token, and builds a `TokenIndex` mapping token -> posting list.
+///
+/// Errors if an entity can't be serialized to JSON - e.g. a `DynamicEntity::Entityf64`
+/// carrying a non-finite value, which `serde_json` has no representation for.
+fn build_token_index(graph: &[GraphVector]) -> Result<TokenIndex, String> {
+    let mut token_postings: BTreeMap<String, HashMap<String, u64>> = BTreeMap::new();
+
+    for graph_vector in graph {
+        let json = serde_json::to_value(graph_vector)
+            .map_err(|e| format!("Failed to serialize entity for search indexing: {e}"))?;
+        let id = json
+            .get("@id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        collect_tokens_recursive(&json, &id, &mut token_postings);
+    }
+
+    let mut builder = MapBuilder::memory();
+    let mut postings = Vec::with_capacity(token_postings.len());
+    for (index, (token, counts)) in token_postings.into_iter().enumerate() {
+        builder.insert(token, index as u64).unwrap();
+        postings.push(counts.into_iter().collect());
+    }
+
+    Ok(TokenIndex {
+        map: builder.into_map(),
+        postings,
+    })
 }
 
-fn search_and_print_recursive(
+/// Recursively tokenizes every string value in `json`, attributing each token to the
+/// nearest enclosing `@id` (an `EntityId` value round-trips to a plain JSON string, so
+/// no special-casing is needed for it).
+fn collect_tokens_recursive(
     json: &JsonValue,
-    search_value: &str,
-    occurrences: &mut HashMap<(String, String), isize>,
-    location: bool,
+    owning_id: &str,
+    token_postings: &mut BTreeMap<String, HashMap<String, u64>>,
 ) {
     match json {
         JsonValue::Object(obj) => {
-            // Retrieve @id if it exists in the current object
             let current_id = obj
                 .get("@id")
                 .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-
+                .unwrap_or(owning_id);
             for (_key, value) in obj {
-                // Check if this value matches the search_value
-                if value == search_value {
-                    let key = (current_id.clone(), search_value.to_string());
-                    *occurrences.entry(key).or_insert(0) += 1;
-
-                    if location {
-                        println!(
-                            "Found in object:\n{}\n",
-                            serde_json::to_string_pretty(&json).unwrap()
-                        );
-                    }
-                }
-                // Recursively search the object
-                search_and_print_recursive(value, search_value, occurrences, location);
+                collect_tokens_recursive(value, current_id, token_postings);
             }
         }
         JsonValue::Array(arr) => {
             for item in arr {
-                search_and_print_recursive(item, search_value, occurrences, location);
+                collect_tokens_recursive(item, owning_id, token_postings);
             }
         }
-        _ => {
-            // For simple values, compare directly
-            if json == search_value {
-                let key = ("N/A".to_string(), search_value.to_string());
-                *occurrences.entry(key).or_insert(0) += 1;
-
-                if location {
-                    println!(
-                        "Found in value:\n{}\n",
-                        serde_json::to_string_pretty(&json).unwrap()
-                    );
+        JsonValue::String(value) => {
+            for token in value.split_whitespace() {
+                let folded = token.to_lowercase();
+                *token_postings
+                    .entry(folded)
+                    .or_default()
+                    .entry(owning_id.to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fuzzy-matches `query` against the index within `distance` edits, unions the
+/// matching tokens' posting lists per `@id`, and ranks results by number of distinct
+/// matched tokens, then by total occurrence frequency.
+fn search_index(index: &TokenIndex, query: &str, distance: u32) -> Vec<(String, String, isize)> {
+    let query_folded = query.to_lowercase();
+    let automaton = match Levenshtein::new(&query_folded, distance) {
+        Ok(automaton) => automaton,
+        Err(e) => {
+            eprintln!("Invalid search query: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut per_id: HashMap<String, (BTreeSet<String>, u64)> = HashMap::new();
+    let mut stream = index.map.search(&automaton).into_stream();
+    while let Some((token_bytes, posting_index)) = stream.next() {
+        let token = String::from_utf8_lossy(token_bytes).to_string();
+        for (id, count) in &index.postings[posting_index as usize] {
+            let entry = per_id
+                .entry(id.clone())
+                .or_insert_with(|| (BTreeSet::new(), 0));
+            entry.0.insert(token.clone());
+            entry.1 += count;
+        }
+    }
+
+    let mut results: Vec<(String, BTreeSet<String>, u64)> = per_id
+        .into_iter()
+        .map(|(id, (tokens, frequency))| (id, tokens, frequency))
+        .collect();
+    results.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(b.2.cmp(&a.2)));
+
+    results
+        .into_iter()
+        .map(|(id, tokens, frequency)| {
+            let value = tokens.into_iter().collect::<Vec<_>>().join(", ");
+            (id, value, frequency as isize)
+        })
+        .collect()
+}
+
+/// How serious a validation finding is: `Error` means the crate fails RO-Crate
+/// conformance, `Warning` means it's missing something recommended but not required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// One conformance finding from `run_validation`: machine-readable enough to drive a
+/// CI gate or an editor problem matcher, instead of the all-or-nothing boolean
+/// `ValidateCommand::Basic` used to print.
+#[derive(Debug, Clone, Serialize)]
+struct Diagnostic {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    entity_id: Option<String>,
+    json_path: String,
+}
+
+/// Runs every RO-Crate conformance check over `rocrate`, collecting every problem
+/// found instead of stopping at the first one.
+fn run_validation(rocrate: &RoCrate) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let known_ids: std::collections::HashSet<&String> =
+        rocrate.graph.iter().map(|gv| gv.get_id()).collect();
+
+    let mut has_descriptor = false;
+    let mut has_root = false;
+
+    for graph_vector in &rocrate.graph {
+        let id = graph_vector.get_id().clone();
+        let json_path = format!("$.graph[@id={}]", id);
+
+        match graph_vector {
+            GraphVector::MetadataDescriptor(descriptor) => {
+                has_descriptor = true;
+                match &descriptor.conforms_to {
+                    Id::Id(conforms_to) if conforms_to.to_lowercase().contains("ro/crate") => {}
+                    Id::Id(conforms_to) => diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "unknown-profile",
+                        message: format!(
+                            "Metadata descriptor's conformsTo `{}` doesn't look like a known RO-Crate profile",
+                            conforms_to
+                        ),
+                        entity_id: Some(id.clone()),
+                        json_path: json_path.clone(),
+                    }),
+                    _ => diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "unknown-profile",
+                        message: "Metadata descriptor's conformsTo is not a single @id".to_string(),
+                        entity_id: Some(id.clone()),
+                        json_path: json_path.clone(),
+                    }),
+                }
+            }
+            GraphVector::RootDataEntity(root) => {
+                has_root = true;
+                if root.id != "./" {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "root-id-mismatch",
+                        message: format!("Root data entity's @id is `{}`, expected `./`", root.id),
+                        entity_id: Some(id.clone()),
+                        json_path: json_path.clone(),
+                    });
+                }
+
+                if DateTime::parse_from_rfc3339(&root.date_published).is_err() {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "invalid-date-published",
+                        message: format!(
+                            "Root data entity's datePublished `{}` is not valid RFC3339",
+                            root.date_published
+                        ),
+                        entity_id: Some(id.clone()),
+                        json_path: json_path.clone(),
+                    });
+                }
+
+                if root.name.trim().is_empty() {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        code: "missing-name",
+                        message: "Root data entity has no name".to_string(),
+                        entity_id: Some(id.clone()),
+                        json_path: json_path.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        for property in graph_vector.get_all_properties() {
+            if let Some((_, EntityValue::EntityId(Id::Id(reference)))) =
+                graph_vector.get_specific_property(&property)
+            {
+                if !known_ids.contains(&reference) {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "dangling-reference",
+                        message: format!(
+                            "`{}` references `{}`, which has no matching entity in the graph",
+                            id, reference
+                        ),
+                        entity_id: Some(id.clone()),
+                        json_path: format!("{}.{}", json_path, property),
+                    });
+                }
+            }
+        }
+    }
+
+    if !has_descriptor {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: "missing-metadata-descriptor",
+            message: "Crate has no metadata descriptor entity".to_string(),
+            entity_id: None,
+            json_path: "$.graph".to_string(),
+        });
+    }
+    if !has_root {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: "missing-root-data-entity",
+            message: "Crate has no root data entity".to_string(),
+            entity_id: None,
+            json_path: "$.graph".to_string(),
+        });
+    }
+
+    diagnostics
+}
+
+/// Prints a diagnostic report as a human-readable table.
+fn print_diagnostics(diagnostics: &[Diagnostic]) {
+    if diagnostics.is_empty() {
+        println!("Crate Valid");
+        return;
+    }
+
+    for diagnostic in diagnostics {
+        println!(
+            "[{}] {} ({}){}",
+            diagnostic.severity,
+            diagnostic.message,
+            diagnostic.code,
+            diagnostic
+                .entity_id
+                .as_ref()
+                .map(|id| format!(" at {}", id))
+                .unwrap_or_default()
+        );
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    println!("\n{} error(s), {} warning(s)", errors, warnings);
+}
+
+/// A single clause of a `ReadCommand::Query` selector.
+#[derive(Debug, Clone)]
+enum QueryPredicate {
+    /// `type:<term>` - entity's `@type` contains this term.
+    Type(String),
+    /// `field:<key>=<value>` - entity's dynamic property `key` equals `value`.
+    Field { key: String, value: String },
+    /// `ref:<id>` - entity references `id` through an `EntityValue::EntityId`.
+    Ref(String),
+}
+
+/// Parses a `&`-combinable selector like `type:Dataset&field:license=MIT` into a
+/// predicate AST, evaluated against the typed `GraphVector` graph rather than a
+/// serde round-trip.
+fn parse_query(selector: &str) -> Result<Vec<QueryPredicate>, String> {
+    selector
+        .split('&')
+        .map(|clause| {
+            if let Some(term) = clause.strip_prefix("type:") {
+                Ok(QueryPredicate::Type(term.to_string()))
+            } else if let Some(rest) = clause.strip_prefix("field:") {
+                match rest.split_once('=') {
+                    Some((key, value)) => Ok(QueryPredicate::Field {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    }),
+                    None => Err(format!(
+                        "Invalid field selector `{}`, expected field:<key>=<value>",
+                        clause
+                    )),
+                }
+            } else if let Some(id) = clause.strip_prefix("ref:") {
+                Ok(QueryPredicate::Ref(id.to_string()))
+            } else {
+                Err(format!(
+                    "Unknown selector clause `{}` (expected type:/field:/ref:)",
+                    clause
+                ))
+            }
+        })
+        .collect()
+}
+
+/// The `@type` term(s) for any `GraphVector` variant.
+pub(crate) fn graph_vector_type(graph_vector: &GraphVector) -> &DataType {
+    match graph_vector {
+        GraphVector::MetadataDescriptor(e) => &e.type_,
+        GraphVector::RootDataEntity(e) => &e.type_,
+        GraphVector::DataEntity(e) => &e.type_,
+        GraphVector::ContextualEntity(e) => &e.type_,
+    }
+}
+
+fn graph_vector_type_matches(graph_vector: &GraphVector, term: &str) -> bool {
+    match graph_vector_type(graph_vector) {
+        DataType::Term(t) => t == term,
+        DataType::TermArray(terms) => terms.iter().any(|t| t == term),
+    }
+}
+
+/// The dynamic (non-typed) properties for any `GraphVector` variant.
+pub(crate) fn graph_vector_dynamic_entity(
+    graph_vector: &GraphVector,
+) -> Option<&HashMap<String, DynamicEntity>> {
+    match graph_vector {
+        GraphVector::MetadataDescriptor(e) => e.dynamic_entity.as_ref(),
+        GraphVector::RootDataEntity(e) => e.dynamic_entity.as_ref(),
+        GraphVector::DataEntity(e) => e.dynamic_entity.as_ref(),
+        GraphVector::ContextualEntity(e) => e.dynamic_entity.as_ref(),
+    }
+}
+
+fn graph_vector_field_matches(graph_vector: &GraphVector, key: &str, value: &str) -> bool {
+    graph_vector_dynamic_entity(graph_vector)
+        .and_then(|fields| fields.get(key))
+        .map(|field| matches!(field, DynamicEntity::EntityString(s) if s == value))
+        .unwrap_or(false)
+}
+
+fn graph_vector_references(graph_vector: &GraphVector, target_id: &str) -> bool {
+    graph_vector
+        .get_all_properties()
+        .into_iter()
+        .filter_map(|property| graph_vector.get_specific_property(&property))
+        .any(|(_, value)| match value {
+            EntityValue::EntityId(Id::Id(id)) => id == target_id,
+            EntityValue::EntityId(Id::IdArray(ids)) => ids.iter().any(|id| id == target_id),
+            _ => false,
+        })
+}
+
+/// Filters the graph to entities satisfying every predicate (selectors combine with `&`).
+fn evaluate_query<'a>(
+    rocrate: &'a RoCrate,
+    predicates: &[QueryPredicate],
+) -> Vec<&'a GraphVector> {
+    rocrate
+        .graph
+        .iter()
+        .filter(|graph_vector| {
+            predicates.iter().all(|predicate| match predicate {
+                QueryPredicate::Type(term) => graph_vector_type_matches(graph_vector, term),
+                QueryPredicate::Field { key, value } => {
+                    graph_vector_field_matches(graph_vector, key, value)
+                }
+                QueryPredicate::Ref(id) => graph_vector_references(graph_vector, id),
+            })
+        })
+        .collect()
+}
+
+/// Follows every matched entity's `EntityValue::EntityId` references one hop and
+/// inlines the referenced entities alongside the original matches (`--expand`).
+fn expand_query_results<'a>(
+    rocrate: &'a RoCrate,
+    matched: Vec<&'a GraphVector>,
+) -> Vec<&'a GraphVector> {
+    let mut seen: HashSet<String> = matched.iter().map(|gv| gv.get_id().clone()).collect();
+    let mut expanded = matched.clone();
+
+    for graph_vector in &matched {
+        let references: Vec<String> = graph_vector
+            .get_all_properties()
+            .into_iter()
+            .filter_map(|property| graph_vector.get_specific_property(&property))
+            .flat_map(|(_, value)| match value {
+                EntityValue::EntityId(Id::Id(id)) => vec![id],
+                EntityValue::EntityId(Id::IdArray(ids)) => ids,
+                _ => Vec::new(),
+            })
+            .collect();
+
+        for id in references {
+            if seen.insert(id.clone()) {
+                if let Some(referenced) = rocrate.get_entity(&id) {
+                    expanded.push(referenced);
                 }
             }
         }
     }
+
+    expanded
 }
@@ -0,0 +1,513 @@
+//! Native `#[pyclass]` wrappers around the core entity types.
+//!
+//! These give Python callers identity, equality, and hashing on top of
+//! `DataEntity`/`ContextualEntity`/`RootDataEntity`/`MetadataDescriptor`,
+//! rather than the anonymous `PyDict`s produced by `utils::base_entity_to_pydict`
+//! and friends. Pickling round-trips `DataEntity` and `ContextualEntity` only:
+//! `RootDataEntity` and `MetadataDescriptor` define `__getstate__`/`__setstate__` but
+//! have no Python constructor, so `pickle.loads` and `copy.deepcopy` of them fail.
+//! `to_pydict`/`from_pydict` are kept around on each wrapper as an
+//! explicit escape hatch to that legacy dict interface.
+
+use crate::utils::{self, ContextualEntityWrapper, DataEntityWrapper, MetadataDescriptorWrapper, RootDataEntityWrapper};
+use ::rocraters::ro_crate::contextual_entity::ContextualEntity;
+use ::rocraters::ro_crate::data_entity::DataEntity;
+use ::rocraters::ro_crate::{
+    constraints::DataType, metadata_descriptor::MetadataDescriptor, root::RootDataEntity,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyBytes;
+use pyo3::{prelude::*, types::PyString};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn serde_err(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(format!("failed to (de)serialize entity: {e}"))
+}
+
+fn data_type_to_pyobject(py: Python, type_: &DataType) -> PyObject {
+    match type_ {
+        DataType::Term(term) => PyString::new(py, term).into(),
+        DataType::TermArray(terms) => {
+            pyo3::types::PyList::new(py, terms.iter().map(|t| PyString::new(py, t)))
+                .expect("a list of PyStrings cannot fail to build")
+                .into()
+        }
+    }
+}
+
+fn data_type_from_pyobject(obj: &Bound<'_, PyAny>) -> PyResult<DataType> {
+    if let Ok(s) = obj.extract::<String>() {
+        Ok(DataType::Term(s))
+    } else if let Ok(terms) = obj.extract::<Vec<String>>() {
+        Ok(DataType::TermArray(terms))
+    } else {
+        Err(PyValueError::new_err(
+            "'type' must be a string or a list of strings",
+        ))
+    }
+}
+
+fn hash_id(id: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// PyO3-native wrapper around [`DataEntity`], e.g. a file or other research artefact.
+#[pyclass(name = "DataEntity")]
+#[derive(Debug)]
+pub struct PyDataEntity {
+    pub(crate) inner: DataEntity,
+}
+
+#[pymethods]
+impl PyDataEntity {
+    #[new]
+    fn new(id: String, type_: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyDataEntity {
+            inner: DataEntity {
+                id,
+                type_: data_type_from_pyobject(type_)?,
+                dynamic_entity: None,
+            },
+        })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    #[setter]
+    fn set_id(&mut self, id: String) {
+        self.inner.id = id;
+    }
+
+    #[getter(type)]
+    fn get_type(&self, py: Python) -> PyObject {
+        data_type_to_pyobject(py, &self.inner.type_)
+    }
+
+    #[setter(type)]
+    fn set_type(&mut self, type_: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.inner.type_ = data_type_from_pyobject(type_)?;
+        Ok(())
+    }
+
+    /// Gets a named dynamic (non-core) property, or `None` if unset.
+    fn get_property(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match self.inner.dynamic_entity.as_ref().and_then(|m| m.get(key)) {
+            Some(value) => utils::convert_dynamic_entity_to_pyobject(py, value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Sets a named dynamic (non-core) property.
+    fn set_property(&mut self, py: Python, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = utils::convert_pyobject_to_dynamic_entity(py, value)?;
+        self.inner
+            .dynamic_entity
+            .get_or_insert_with(std::collections::HashMap::new)
+            .insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Lists the names of the dynamic (non-core) properties currently set.
+    fn keys(&self) -> Vec<String> {
+        self.inner
+            .dynamic_entity
+            .as_ref()
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Converts to the legacy `PyDict` representation.
+    fn to_pydict(&self, py: Python) -> PyResult<PyObject> {
+        utils::base_entity_to_pydict(py, &self.inner)
+    }
+
+    /// Builds a `DataEntity` from the legacy dict representation.
+    #[staticmethod]
+    fn from_pydict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let wrapper: DataEntityWrapper = obj.extract()?;
+        Ok(PyDataEntity { inner: wrapper.0 })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DataEntity(id={:?}, type={:?})",
+            self.inner.id, self.inner.type_
+        )
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner.id == other.inner.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_id(&self.inner.id)
+    }
+
+    fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let bytes = serde_json::to_vec(&self.inner).map_err(serde_err)?;
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    fn __setstate__(&mut self, state: &Bound<'_, PyBytes>) -> PyResult<()> {
+        self.inner = serde_json::from_slice(state.as_bytes()).map_err(serde_err)?;
+        Ok(())
+    }
+
+    fn __getnewargs__(&self, py: Python) -> (String, PyObject) {
+        (self.inner.id.clone(), data_type_to_pyobject(py, &self.inner.type_))
+    }
+}
+
+/// PyO3-native wrapper around [`ContextualEntity`], e.g. a person or organization.
+#[pyclass(name = "ContextualEntity")]
+#[derive(Debug)]
+pub struct PyContextualEntity {
+    pub(crate) inner: ContextualEntity,
+}
+
+#[pymethods]
+impl PyContextualEntity {
+    #[new]
+    fn new(id: String, type_: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyContextualEntity {
+            inner: ContextualEntity {
+                id,
+                type_: data_type_from_pyobject(type_)?,
+                dynamic_entity: None,
+            },
+        })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    #[setter]
+    fn set_id(&mut self, id: String) {
+        self.inner.id = id;
+    }
+
+    #[getter(type)]
+    fn get_type(&self, py: Python) -> PyObject {
+        data_type_to_pyobject(py, &self.inner.type_)
+    }
+
+    #[setter(type)]
+    fn set_type(&mut self, type_: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.inner.type_ = data_type_from_pyobject(type_)?;
+        Ok(())
+    }
+
+    /// Gets a named dynamic (non-core) property, or `None` if unset.
+    fn get_property(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match self.inner.dynamic_entity.as_ref().and_then(|m| m.get(key)) {
+            Some(value) => utils::convert_dynamic_entity_to_pyobject(py, value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Sets a named dynamic (non-core) property.
+    fn set_property(&mut self, py: Python, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = utils::convert_pyobject_to_dynamic_entity(py, value)?;
+        self.inner
+            .dynamic_entity
+            .get_or_insert_with(std::collections::HashMap::new)
+            .insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Lists the names of the dynamic (non-core) properties currently set.
+    fn keys(&self) -> Vec<String> {
+        self.inner
+            .dynamic_entity
+            .as_ref()
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn to_pydict(&self, py: Python) -> PyResult<PyObject> {
+        utils::base_entity_to_pydict(py, &self.inner)
+    }
+
+    #[staticmethod]
+    fn from_pydict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let wrapper: ContextualEntityWrapper = obj.extract()?;
+        Ok(PyContextualEntity { inner: wrapper.0 })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ContextualEntity(id={:?}, type={:?})",
+            self.inner.id, self.inner.type_
+        )
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner.id == other.inner.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_id(&self.inner.id)
+    }
+
+    fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let bytes = serde_json::to_vec(&self.inner).map_err(serde_err)?;
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    fn __setstate__(&mut self, state: &Bound<'_, PyBytes>) -> PyResult<()> {
+        self.inner = serde_json::from_slice(state.as_bytes()).map_err(serde_err)?;
+        Ok(())
+    }
+
+    fn __getnewargs__(&self, py: Python) -> (String, PyObject) {
+        (self.inner.id.clone(), data_type_to_pyobject(py, &self.inner.type_))
+    }
+}
+
+/// PyO3-native wrapper around [`RootDataEntity`], the crate's root dataset description.
+#[pyclass(name = "RootDataEntity")]
+#[derive(Debug)]
+pub struct PyRootDataEntity {
+    pub(crate) inner: RootDataEntity,
+}
+
+#[pymethods]
+impl PyRootDataEntity {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    #[setter]
+    fn set_id(&mut self, id: String) {
+        self.inner.id = id;
+    }
+
+    #[getter(type)]
+    fn get_type(&self, py: Python) -> PyObject {
+        data_type_to_pyobject(py, &self.inner.type_)
+    }
+
+    #[setter(type)]
+    fn set_type(&mut self, type_: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.inner.type_ = data_type_from_pyobject(type_)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[setter]
+    fn set_name(&mut self, name: String) {
+        self.inner.name = name;
+    }
+
+    #[getter]
+    fn description(&self) -> String {
+        self.inner.description.clone()
+    }
+
+    #[setter]
+    fn set_description(&mut self, description: String) {
+        self.inner.description = description;
+    }
+
+    #[getter(datePublished)]
+    fn date_published(&self) -> String {
+        self.inner.date_published.clone()
+    }
+
+    #[setter(datePublished)]
+    fn set_date_published(&mut self, date_published: String) {
+        self.inner.date_published = date_published;
+    }
+
+    #[getter]
+    fn license(&self, py: Python) -> PyObject {
+        utils::convert_license_to_pyobject(py, &self.inner.license)
+    }
+
+    /// Gets a named dynamic (non-core) property, or `None` if unset.
+    fn get_property(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match self.inner.dynamic_entity.as_ref().and_then(|m| m.get(key)) {
+            Some(value) => utils::convert_dynamic_entity_to_pyobject(py, value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Sets a named dynamic (non-core) property.
+    fn set_property(&mut self, py: Python, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = utils::convert_pyobject_to_dynamic_entity(py, value)?;
+        self.inner
+            .dynamic_entity
+            .get_or_insert_with(std::collections::HashMap::new)
+            .insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Lists the names of the dynamic (non-core) properties currently set.
+    fn keys(&self) -> Vec<String> {
+        self.inner
+            .dynamic_entity
+            .as_ref()
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn to_pydict(&self, py: Python) -> PyResult<PyObject> {
+        utils::root_entity_to_pydict(py, &self.inner)
+    }
+
+    #[staticmethod]
+    fn from_pydict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let wrapper: RootDataEntityWrapper = obj.extract()?;
+        Ok(PyRootDataEntity { inner: wrapper.0 })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RootDataEntity(id={:?}, name={:?})",
+            self.inner.id, self.inner.name
+        )
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner.id == other.inner.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_id(&self.inner.id)
+    }
+
+    fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let bytes = serde_json::to_vec(&self.inner).map_err(serde_err)?;
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    fn __setstate__(&mut self, state: &Bound<'_, PyBytes>) -> PyResult<()> {
+        self.inner = serde_json::from_slice(state.as_bytes()).map_err(serde_err)?;
+        Ok(())
+    }
+}
+
+/// PyO3-native wrapper around [`MetadataDescriptor`], the `ro-crate-metadata.json` self-description.
+#[pyclass(name = "MetadataDescriptor")]
+#[derive(Debug)]
+pub struct PyMetadataDescriptor {
+    pub(crate) inner: MetadataDescriptor,
+}
+
+#[pymethods]
+impl PyMetadataDescriptor {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    #[setter]
+    fn set_id(&mut self, id: String) {
+        self.inner.id = id;
+    }
+
+    #[getter(type)]
+    fn get_type(&self, py: Python) -> PyObject {
+        data_type_to_pyobject(py, &self.inner.type_)
+    }
+
+    #[setter(type)]
+    fn set_type(&mut self, type_: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.inner.type_ = data_type_from_pyobject(type_)?;
+        Ok(())
+    }
+
+    #[getter(conformsTo)]
+    fn conforms_to(&self, py: Python) -> PyResult<PyObject> {
+        utils::convert_id_to_pyobject(py, &self.inner.conforms_to)
+    }
+
+    #[getter]
+    fn about(&self, py: Python) -> PyResult<PyObject> {
+        utils::convert_id_to_pyobject(py, &self.inner.about)
+    }
+
+    /// Gets a named dynamic (non-core) property, or `None` if unset.
+    fn get_property(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match self.inner.dynamic_entity.as_ref().and_then(|m| m.get(key)) {
+            Some(value) => utils::convert_dynamic_entity_to_pyobject(py, value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Sets a named dynamic (non-core) property.
+    fn set_property(&mut self, py: Python, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = utils::convert_pyobject_to_dynamic_entity(py, value)?;
+        self.inner
+            .dynamic_entity
+            .get_or_insert_with(std::collections::HashMap::new)
+            .insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Lists the names of the dynamic (non-core) properties currently set.
+    fn keys(&self) -> Vec<String> {
+        self.inner
+            .dynamic_entity
+            .as_ref()
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn to_pydict(&self, py: Python) -> PyResult<PyObject> {
+        utils::metadata_descriptor_to_pydict(py, &self.inner)
+    }
+
+    #[staticmethod]
+    fn from_pydict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let wrapper: MetadataDescriptorWrapper = obj.extract()?;
+        Ok(PyMetadataDescriptor { inner: wrapper.0 })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MetadataDescriptor(id={:?}, conformsTo={:?})",
+            self.inner.id, self.inner.conforms_to
+        )
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner.id == other.inner.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_id(&self.inner.id)
+    }
+
+    fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let bytes = serde_json::to_vec(&self.inner).map_err(serde_err)?;
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    fn __setstate__(&mut self, state: &Bound<'_, PyBytes>) -> PyResult<()> {
+        self.inner = serde_json::from_slice(state.as_bytes()).map_err(serde_err)?;
+        Ok(())
+    }
+}
+
+/// Registers the PyClasses above with the `rocraters` Python module.
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDataEntity>()?;
+    m.add_class::<PyContextualEntity>()?;
+    m.add_class::<PyRootDataEntity>()?;
+    m.add_class::<PyMetadataDescriptor>()?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 //! Python bindings for ro-crate-rs core
 
+mod entities;
 mod utils;
 extern crate chrono;
 use ::rocraters::ro_crate::constraints::*;
@@ -14,7 +15,7 @@ use chrono::prelude::*;
 use pyo3::exceptions::PyIOError;
 use pyo3::{
     prelude::*,
-    types::{PyDict, PyList, PyString},
+    types::{PyDict, PyList, PyModule, PyString},
 };
 use std::collections::HashMap;
 use std::path::Path;
@@ -39,7 +40,7 @@ impl PyRoCrateContext {
     ///
     /// Used for creating a base RoCrate vocab
     #[staticmethod]
-    fn from_string(context: &PyString) -> Self {
+    fn from_string(context: &Bound<'_, PyString>) -> Self {
         PyRoCrateContext {
             inner: RoCrateContext::ReferenceContext(context.to_string()),
         }
@@ -49,15 +50,15 @@ impl PyRoCrateContext {
     ///
     /// Allows for a Reference, Embedded and Extended RoCrate context.
     #[staticmethod]
-    fn from_list(context: &PyList) -> PyResult<Self> {
+    fn from_list(context: &Bound<'_, PyList>) -> PyResult<Self> {
         let mut context_items = Vec::new();
         for obj in context.iter() {
             // Check if obj is a string or a dict
             if let Ok(string) = obj.extract::<String>() {
                 context_items.push(ContextItem::ReferenceItem(string));
-            } else if let Ok(dict) = obj.extract::<&PyDict>() {
+            } else if let Ok(dict) = obj.downcast::<PyDict>() {
                 let mut map = HashMap::new();
-                for (key, val) in dict.into_iter() {
+                for (key, val) in dict.iter() {
                     let key_str: String = key.extract()?;
                     let val_str: String = val.extract()?;
                     map.insert(key_str, val_str);
@@ -209,6 +210,53 @@ impl PyRoCrate {
         Ok(())
     }
 
+    /// Serializes the crate to a pretty-printed JSON string, without touching disk.
+    ///
+    /// Symmetric with `read_obj`, so a crate can round-trip through memory (e.g. across
+    /// a network boundary or into another library) without ever hitting the filesystem.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.inner).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Failed to serialize crate: {e}"
+            ))
+        })
+    }
+
+    /// Audits the crate without fully deserializing every entity.
+    ///
+    /// Returns a dict with `entities` (each with `id`, `type`, `references`),
+    /// `data_entity_count`, `contextual_entity_count`, and `dangling_references` — a cheap
+    /// way to spot broken links before editing a large crate.
+    fn summary(&self, py: Python) -> PyResult<PyObject> {
+        let summary = self.inner.inspect();
+
+        let entities = PyList::empty(py);
+        for entity in &summary.entities {
+            let entry = PyDict::new(py);
+            entry.set_item("id", &entity.id)?;
+            entry.set_item("type", &entity.type_)?;
+            entry.set_item("references", entity.references.clone())?;
+            entities.append(entry)?;
+        }
+
+        let result = PyDict::new(py);
+        result.set_item("entities", entities)?;
+        result.set_item("data_entity_count", summary.data_entity_count)?;
+        result.set_item("contextual_entity_count", summary.contextual_entity_count)?;
+        result.set_item("dangling_references", summary.dangling_references)?;
+
+        Ok(result.into())
+    }
+
+    /// Serializes the crate to JSON bytes, without touching disk.
+    ///
+    /// Useful when the caller wants to stream or hash the serialized crate rather than
+    /// hold it as a `str`.
+    fn to_bytes(&self, py: Python) -> PyResult<PyObject> {
+        let json_ld = self.to_json()?;
+        Ok(pyo3::types::PyBytes::new(py, json_ld.as_bytes()).into())
+    }
+
     /// Print's full crate
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("PyRoCrate(data: '{:#?}')", self.inner))
@@ -300,9 +348,10 @@ impl Default for PyRoCrate {
 
 /// A lightweight Python library for Ro-Crate manipulation implemented in Rust.
 #[pymodule]
-fn rocraters(_py: Python, m: &PyModule) -> PyResult<()> {
+fn rocraters(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyRoCrate>()?;
     m.add_class::<PyRoCrateContext>()?;
+    entities::register(m)?;
     m.add_function(wrap_pyfunction!(read, m)?)?;
     m.add_function(wrap_pyfunction!(read_obj, m)?)?;
     m.add_function(wrap_pyfunction!(zip, m)?)?;
@@ -7,13 +7,78 @@ use ::rocraters::ro_crate::{
     metadata_descriptor::MetadataDescriptor,
     root::RootDataEntity,
 };
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::PyValueError;
 use pyo3::{
     prelude::*,
     types::{PyBool, PyDict, PyFloat, PyList, PyString},
 };
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+
+/// Error accumulated while converting between the serde model and Python
+/// objects. Each recursion level prepends the path segment it was responsible
+/// for - an entity id, a property key, or an array index - via [`ConversionError::at`],
+/// so a failure deep in a nested structure surfaces as one readable path
+/// (e.g. `ConversionError at '#dataset-1'.author[2].affiliation: ...`) instead
+/// of an opaque panic.
+#[derive(Debug, Clone)]
+pub struct ConversionError {
+    path: Vec<String>,
+    message: String,
+}
+
+impl ConversionError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ConversionError {
+            path: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Prepends a path segment as the error unwinds back through nested
+    /// entities/properties/indices. Segments already carry their own
+    /// punctuation (`'id'`, `.key`, `[index]`) so they can be concatenated
+    /// directly in call order.
+    pub fn at(mut self, segment: impl Into<String>) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "ConversionError at {}: {}", self.path.concat(), self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<PyErr> for ConversionError {
+    fn from(err: PyErr) -> Self {
+        ConversionError::new(err.to_string())
+    }
+}
+
+impl From<ConversionError> for PyErr {
+    fn from(err: ConversionError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+type ConversionResult<T> = Result<T, ConversionError>;
+
+/// Fetches a required key from a dict, turning a missing key into a
+/// [`ConversionError`] instead of the `Option::unwrap()` panic this used to be.
+fn get_required<'py>(dict: &Bound<'py, PyDict>, key: &str) -> ConversionResult<Bound<'py, PyAny>> {
+    dict.get_item(key)
+        .map_err(ConversionError::from)?
+        .ok_or_else(|| ConversionError::new(format!("missing required key '{key}'")))
+}
 
 pub trait EntityTrait {
     fn id(&self) -> &str;
@@ -51,7 +116,7 @@ impl EntityTrait for ContextualEntity {
 
 /// Converts base entities (data and contextual) to python dicts
 pub fn base_entity_to_pydict<T: EntityTrait>(py: Python, entity: &T) -> PyResult<PyObject> {
-    let py_dict = PyDict::new_bound(py);
+    let py_dict = PyDict::new(py);
 
     // Now use the shared trait methods to access fields.
     py_dict.set_item("id", entity.id())?;
@@ -63,9 +128,9 @@ pub fn base_entity_to_pydict<T: EntityTrait>(py: Python, entity: &T) -> PyResult
         DataType::TermArray(terms) => {
             let py_terms = terms
                 .iter()
-                .map(|term| PyString::new_bound(py, term))
+                .map(|term| PyString::new(py, term))
                 .collect::<Vec<_>>();
-            py_dict.set_item("type", PyList::new_bound(py, &py_terms))?;
+            py_dict.set_item("type", PyList::new(py, &py_terms)?)?;
         }
     }
 
@@ -73,7 +138,7 @@ pub fn base_entity_to_pydict<T: EntityTrait>(py: Python, entity: &T) -> PyResult
     if let Some(dynamic_entity) = entity.dynamic_entity() {
         for (key, value) in dynamic_entity.iter() {
             // Convert each DynamicEntity to a PyObject and insert it directly into py_dict
-            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value))?;
+            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value)?)?;
         }
     }
 
@@ -82,34 +147,32 @@ pub fn base_entity_to_pydict<T: EntityTrait>(py: Python, entity: &T) -> PyResult
 
 /// Converts root metadata entity to py dict
 pub fn root_entity_to_pydict(py: Python, entity: &RootDataEntity) -> PyResult<PyObject> {
-    let py_dict = PyDict::new_bound(py);
+    let py_dict = PyDict::new(py);
 
     py_dict.set_item("id", &entity.id)?;
 
     match &entity.type_ {
         DataType::Term(term) => {
-            py_dict.set_item("type", term).unwrap();
+            py_dict.set_item("type", term)?;
         }
         DataType::TermArray(terms) => {
             let py_terms = terms
                 .iter()
-                .map(|term| PyString::new_bound(py, term))
+                .map(|term| PyString::new(py, term))
                 .collect::<Vec<_>>();
-            py_dict.set_item("type", py_terms).unwrap();
+            py_dict.set_item("type", PyList::new(py, &py_terms)?)?;
         }
     }
     py_dict.set_item("name", &entity.name)?;
     py_dict.set_item("description", &entity.description)?;
     py_dict.set_item("datePublished", &entity.date_published)?;
-
-    let license_py_object = convert_license_to_pyobject(py, &entity.license);
-    py_dict.set_item("license", license_py_object).unwrap();
+    py_dict.set_item("license", convert_license_to_pyobject(py, &entity.license))?;
 
     // Directly add dynamic_entity entries to the base dictionary
     if let Some(dynamic_entity) = &entity.dynamic_entity {
         for (key, value) in dynamic_entity.iter() {
             // Convert each DynamicEntity to a PyObject and insert it directly into py_dict
-            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value))?;
+            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value)?)?;
         }
     }
 
@@ -121,442 +184,428 @@ pub fn metadata_descriptor_to_pydict(
     py: Python,
     descriptor: &MetadataDescriptor,
 ) -> PyResult<PyObject> {
-    let py_dict = PyDict::new_bound(py);
+    let py_dict = PyDict::new(py);
 
     py_dict.set_item("id", &descriptor.id)?;
 
     match &descriptor.type_ {
         DataType::Term(term) => {
-            py_dict.set_item("type", term).unwrap();
+            py_dict.set_item("type", term)?;
         }
         DataType::TermArray(terms) => {
             let py_terms = terms
                 .iter()
-                .map(|term| PyString::new_bound(py, term))
+                .map(|term| PyString::new(py, term))
                 .collect::<Vec<_>>();
-            py_dict.set_item("type", py_terms).unwrap();
+            py_dict.set_item("type", PyList::new(py, &py_terms)?)?;
         }
     }
 
-    let py_object = convert_id_to_pyobject(py, &descriptor.conforms_to)
-        .expect("Failed to convert Id to PyObject");
-    py_dict.set_item("conformsTo", py_object).unwrap();
-
-    let py_object =
-        convert_id_to_pyobject(py, &descriptor.about).expect("Failed to convert Id to PyObject");
-    py_dict.set_item("about", py_object).unwrap();
+    py_dict.set_item("conformsTo", convert_id_to_pyobject(py, &descriptor.conforms_to)?)?;
+    py_dict.set_item("about", convert_id_to_pyobject(py, &descriptor.about)?)?;
 
     // Directly add dynamic_entity entries to the base dictionary
     if let Some(dynamic_entity) = &descriptor.dynamic_entity {
         for (key, value) in dynamic_entity.iter() {
             // Convert each DynamicEntity to a PyObject and insert it directly into py_dict
-            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value))?;
+            py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, value)?)?;
         }
     }
 
     Ok(py_dict.into())
 }
 
-/// Converts a license type to a pyobject
+/// Converts a license type to a pyobject. `PyList::new` is only fallible when an
+/// element's own conversion fails, which can't happen for the already-built
+/// `PyString`s here, so the call site below is annotated accordingly
+/// rather than threading a `PyResult` through a function that cannot otherwise fail.
 pub fn convert_license_to_pyobject(py: Python, license_opt: &License) -> PyObject {
     match license_opt {
         License::Id(id_enum) => match id_enum {
-            Id::Id(id_value) => PyString::new_bound(py, &id_value.id).into_py(py),
+            Id::Id(id_value) => PyString::new(py, &id_value.id).into_py(py),
             Id::IdArray(id_values) => {
-                let py_list = PyList::new_bound(
+                let py_list = PyList::new(
                     py,
-                    id_values
-                        .iter()
-                        .map(|id_val| PyString::new_bound(py, &id_val.id)),
-                );
+                    id_values.iter().map(|id_val| PyString::new(py, &id_val.id)),
+                )
+                .expect("a list of PyStrings cannot fail to build");
                 py_list.into()
             }
         },
-        License::Description(description) => PyString::new_bound(py, description).into_py(py),
+        License::Description(description) => PyString::new(py, description).into_py(py),
     }
 }
 
 /// Converts dynamic entities into pyobjects for dict representation
-pub fn convert_dynamic_entity_to_pyobject(py: Python, value: &DynamicEntity) -> PyObject {
+pub fn convert_dynamic_entity_to_pyobject(py: Python, value: &DynamicEntity) -> PyResult<PyObject> {
     match value {
-        DynamicEntity::EntityString(s) => PyString::new_bound(py, s).into(),
+        DynamicEntity::EntityString(s) => Ok(PyString::new(py, s).into()),
         DynamicEntity::EntityVecString(vec) => {
-            let py_list = PyList::new_bound(py, vec.iter().map(|s| PyString::new_bound(py, s)));
-            py_list.into()
+            let py_list = PyList::new(py, vec.iter().map(|s| PyString::new(py, s)))?;
+            Ok(py_list.into())
         }
-        DynamicEntity::EntityId(id) => convert_id_to_pyobject(py, id).unwrap(),
+        DynamicEntity::EntityId(id) => convert_id_to_pyobject(py, id),
         DynamicEntity::EntityIdVec(ids) => {
-            let py_list = PyList::new_bound(
-                py,
-                ids.iter().map(|id| convert_id_to_pyobject(py, id).unwrap()),
-            );
-            py_list.into()
+            let mut py_ids = Vec::with_capacity(ids.len());
+            for id in ids {
+                py_ids.push(convert_id_to_pyobject(py, id)?);
+            }
+            Ok(PyList::new(py, &py_ids)?.into())
         }
         DynamicEntity::EntityBool(b) => {
             match b {
-                Some(value) => PyBool::new_bound(py, *value).into_py(py), // If it's a bool, convert it
-                None => py.None().into_py(py), // If it's None, keep it as None in Python
+                Some(value) => Ok(PyBool::new(py, *value).into_py(py)), // If it's a bool, convert it
+                None => Ok(py.None().into_py(py)), // If it's None, keep it as None in Python
             }
         }
-        DynamicEntity::Entityi64(num) => (*num).into_py(py),
-        DynamicEntity::Entityf64(num) => PyFloat::new_bound(py, *num).into(),
+        DynamicEntity::Entityi64(num) => Ok((*num).into_py(py)),
+        DynamicEntity::Entityf64(num) => Ok(PyFloat::new(py, *num).into()),
         DynamicEntity::EntityVeci64(vec) => {
-            let py_list = PyList::new_bound(py, vec.iter().map(|&num| (num).into_py(py)));
-            py_list.into()
+            let py_list = PyList::new(py, vec.iter().map(|&num| (num).into_py(py)))?;
+            Ok(py_list.into())
         }
         DynamicEntity::EntityVecf64(vec) => {
-            let py_list = PyList::new_bound(py, vec.iter().map(|&num| PyFloat::new_bound(py, num)));
-            py_list.into()
+            let py_list = PyList::new(py, vec.iter().map(|&num| PyFloat::new(py, num)))?;
+            Ok(py_list.into())
         }
         DynamicEntity::EntityVec(vec) => {
-            let py_list = PyList::new_bound(
-                py,
-                vec.iter()
-                    .map(|entity| convert_dynamic_entity_to_pyobject(py, entity)),
-            );
-            py_list.into()
+            let mut items = Vec::with_capacity(vec.len());
+            for entity in vec {
+                items.push(convert_dynamic_entity_to_pyobject(py, entity)?);
+            }
+            Ok(PyList::new(py, &items)?.into())
         }
         DynamicEntity::EntityObject(map) => {
-            let py_dict = PyDict::new_bound(py);
+            let py_dict = PyDict::new(py);
             for (key, val) in map {
-                py_dict
-                    .set_item(key, convert_dynamic_entity_to_pyobject(py, val))
-                    .unwrap();
+                py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, val)?)?;
             }
-            py_dict.into()
+            Ok(py_dict.into())
         }
         DynamicEntity::EntityVecObject(vec) => {
-            let py_list = PyList::new_bound(
-                py,
-                vec.iter().map(|map| {
-                    let py_dict = PyDict::new_bound(py);
-                    for (key, val) in map {
-                        py_dict
-                            .set_item(key, convert_dynamic_entity_to_pyobject(py, val))
-                            .unwrap();
-                    }
-                    py_dict.to_object(py) // Explicitly convert to PyObject
-                }),
-            );
-            py_list.to_object(py) // Convert the PyList to PyObject
+            let mut items = Vec::with_capacity(vec.len());
+            for map in vec {
+                let py_dict = PyDict::new(py);
+                for (key, val) in map {
+                    py_dict.set_item(key, convert_dynamic_entity_to_pyobject(py, val)?)?;
+                }
+                items.push(py_dict.to_object(py));
+            }
+            Ok(PyList::new(py, &items)?.to_object(py))
         }
         DynamicEntity::NestedDynamicEntity(boxed_entity) => {
             convert_dynamic_entity_to_pyobject(py, boxed_entity)
         }
-        DynamicEntity::Fallback(value_option) => {
-            // Convert serde_json::Value to PyObject
-            if let Some(value) = value_option {
-                // Convert serde_json::Value to PyObject when there's a value
-                convert_serde_json_value_to_pyobject(py, value)
-            } else {
-                // Handle the case where Fallback contains None (i.e., represents null)
-                convert_serde_json_value_to_pyobject(py, &serde_json::Value::Null)
-            }
-        }
+        DynamicEntity::Fallback(value_option) => match value_option {
+            Some(value) => convert_serde_json_value_to_pyobject(py, value),
+            None => convert_serde_json_value_to_pyobject(py, &serde_json::Value::Null),
+        },
     }
 }
 
 // Function to handle conversion of serde_json::Value
-pub fn convert_serde_json_value_to_pyobject(py: Python, value: &Value) -> PyObject {
+//
+// Recurses into `Array`/`Object` so nothing nested is lost, and maps `Null` to
+// Python `None` rather than dropping it - this is the `Fallback` variant's
+// only representation of values `DynamicEntity` has no dedicated arm for, so
+// it must round-trip losslessly.
+pub fn convert_serde_json_value_to_pyobject(py: Python, value: &Value) -> PyResult<PyObject> {
     match value {
-        Value::String(s) => PyString::new_bound(py, s).into(),
-        Value::Number(num) => {
-            if let Some(i) = num.as_i64() {
-                i.into_py(py)
-            } else if let Some(f) = num.as_f64() {
-                PyFloat::new_bound(py, f).into()
-            } else {
-                PyString::new_bound(py, &num.to_string()).into()
+        Value::Null => Ok(py.None()),
+        Value::String(s) => Ok(PyString::new(py, s).into()),
+        Value::Number(num) => Ok(if let Some(i) = num.as_i64() {
+            i.into_py(py)
+        } else if let Some(f) = num.as_f64() {
+            PyFloat::new(py, f).into()
+        } else {
+            PyString::new(py, &num.to_string()).into()
+        }),
+        Value::Bool(b) => Ok(PyBool::new(py, *b).into_py(py)),
+        Value::Array(items) => {
+            let mut py_items = Vec::with_capacity(items.len());
+            for item in items {
+                py_items.push(convert_serde_json_value_to_pyobject(py, item)?);
             }
+            Ok(PyList::new(py, &py_items)?.into())
+        }
+        Value::Object(map) => {
+            let py_dict = PyDict::new(py);
+            for (key, val) in map {
+                py_dict.set_item(key, convert_serde_json_value_to_pyobject(py, val)?)?;
+            }
+            Ok(py_dict.into())
         }
-        Value::Bool(b) => PyBool::new_bound(py, *b).into_py(py),
-        // Handle other serde_json::Value types as needed
-        // ...
-        _ => PyString::new_bound(py, "Unsupported serde_json::Value type").into(),
     }
 }
 
 /// Converts an id value to pyobject
-fn convert_id_to_pyobject(py: Python, id: &Id) -> PyResult<PyObject> {
+pub(crate) fn convert_id_to_pyobject(py: Python, id: &Id) -> PyResult<PyObject> {
     match id {
         Id::Id(id_value) => {
-            let py_dict = PyDict::new_bound(py);
-            py_dict.set_item("id", PyString::new_bound(py, &id_value.id))?;
+            let py_dict = PyDict::new(py);
+            py_dict.set_item("id", PyString::new(py, &id_value.id))?;
             Ok(py_dict.into_py(py))
         }
         Id::IdArray(id_values) => {
-            let dicts: Vec<PyObject> = id_values
-                .iter()
-                .map(|id_val| {
-                    let py_dict = PyDict::new_bound(py);
-                    py_dict
-                        .set_item("id", PyString::new_bound(py, &id_val.id))
-                        .expect("Failed to set 'id' key");
-                    py_dict.into_py(py)
-                })
-                .collect();
-
-            let py_list = PyList::new_bound(py, &dicts);
+            let mut dicts: Vec<PyObject> = Vec::with_capacity(id_values.len());
+            for id_val in id_values {
+                let py_dict = PyDict::new(py);
+                py_dict.set_item("id", PyString::new(py, &id_val.id))?;
+                dicts.push(py_dict.into_py(py));
+            }
+
+            let py_list = PyList::new(py, &dicts)?;
             Ok(py_list.into_py(py))
         }
     }
 }
 
+/// Reads the public, non-callable attributes of an arbitrary Python object
+/// (e.g. a `dataclass`, `attrs` class, or pydantic model) via `inspect.getmembers`,
+/// skipping dunders and methods, and lays them out as a `PyDict` - the same shape
+/// the `*Wrapper` extraction logic already expects, so a plain-object input can
+/// be handled by the exact same field-sifting code that a dict input goes through.
+fn object_attrs_as_pydict<'py>(
+    py: Python<'py>,
+    obj: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let inspect = py.import("inspect")?;
+    let members = inspect.call_method1("getmembers", (obj,))?;
+    let members = members.downcast::<PyList>()?;
+
+    let dict = PyDict::new(py);
+    for pair in members.iter() {
+        let (name, value): (String, Bound<'py, PyAny>) = pair.extract()?;
+        if name.starts_with("__") || value.is_callable() {
+            continue;
+        }
+        dict.set_item(name, value)?;
+    }
+    Ok(dict)
+}
+
+/// Sweeps every dict entry whose key is not in `known_keys` into a
+/// `DynamicEntity` map. Shared by every `*Wrapper::extract_bound` impl below so
+/// a struct's declared field list and its dynamic-entity exclusion set are
+/// always the same slice - they can no longer drift apart, which is what
+/// caused the old `MetadataDescriptorWrapper` `conformsTo`/`about` bug.
+fn collect_dynamic_entities(
+    py: Python,
+    py_dict: &Bound<PyDict>,
+    known_keys: &[&str],
+) -> ConversionResult<Option<HashMap<String, DynamicEntity>>> {
+    let mut dynamic_entity_map: HashMap<String, DynamicEntity> = HashMap::new();
+    for (key, value) in py_dict.iter() {
+        let key_str: String = key.extract().map_err(ConversionError::from)?;
+        if !known_keys.contains(&key_str.as_str()) {
+            let dynamic_entity = convert_pyobject_to_dynamic_entity(py, &value)
+                .map_err(|e| e.at(format!(".{key_str}")))?;
+            dynamic_entity_map.insert(key_str, dynamic_entity);
+        }
+    }
+    Ok(if dynamic_entity_map.is_empty() {
+        None
+    } else {
+        Some(dynamic_entity_map)
+    })
+}
+
 //New type pattern for DataEntity
 pub struct DataEntityWrapper(pub DataEntity);
-impl<'source> FromPyObject<'source> for DataEntityWrapper {
-    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+impl DataEntityWrapper {
+    const KNOWN_KEYS: &'static [&'static str] = &["id", "type"];
+}
+impl<'py> FromPyObject<'py> for DataEntityWrapper {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         let py = obj.py(); // Obtain the Python interpreter context from `obj`
-        let py_dict: &PyDict = obj.downcast()?; // Safely cast the PyAny to PyDict
-
-        // Extract the "id" and "type_" fields explicitly
-        let id: String = match py_dict.get_item("id") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
+        let attrs_dict;
+        let py_dict: &Bound<'py, PyDict> = match obj.downcast::<PyDict>() {
+            Ok(dict) => dict,
+            Err(_) => {
+                // Not a dict - fall back to attribute introspection so dataclasses,
+                // attrs classes, and pydantic models can be passed in directly.
+                attrs_dict = object_attrs_as_pydict(py, obj)?;
+                &attrs_dict
+            }
         };
 
-        let type_ = create_data_type_from_dict(py_dict)?;
+        let id: String = get_required(py_dict, "id")?.to_string();
 
-        // Initialize an empty HashMap to hold dynamic_entity entries
-        let mut dynamic_entity_map: HashMap<String, DynamicEntity> = HashMap::new();
-
-        // Iterate over the dictionary, excluding "id" and "type" keys
-        for (key, value) in py_dict.into_iter() {
-            let key_str: String = key.extract()?; // Extract key as String
-            if key_str != "id" && key_str != "type" {
-                let dynamic_entity = convert_pyobject_to_dynamic_entity(py, value)?;
-                // Convert value to DynamicEntity and insert into the map
-                dynamic_entity_map.insert(key_str, dynamic_entity);
-            }
-        }
+        let entity = (|| -> ConversionResult<DataEntity> {
+            let type_ = create_data_type_from_dict(py_dict)?;
+            let dynamic_entity = collect_dynamic_entities(py, py_dict, Self::KNOWN_KEYS)?;
 
-        // Construct DataEntity, wrapping all dynamic entities in Some if not empty, else None
-        let dynamic_entity = if !dynamic_entity_map.is_empty() {
-            Some(dynamic_entity_map)
-        } else {
-            None
-        };
+            Ok(DataEntity {
+                id: id.clone(),
+                type_,
+                dynamic_entity,
+            })
+        })()
+        .map_err(|e| e.at(format!("'{id}'")))?;
 
-        Ok(DataEntityWrapper(DataEntity {
-            id,
-            type_,
-            dynamic_entity,
-        }))
+        Ok(DataEntityWrapper(entity))
     }
 }
 
 //New type pattern for ContextualEntity
 pub struct ContextualEntityWrapper(pub ContextualEntity);
-impl<'source> FromPyObject<'source> for ContextualEntityWrapper {
-    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+impl ContextualEntityWrapper {
+    const KNOWN_KEYS: &'static [&'static str] = &["id", "type"];
+}
+impl<'py> FromPyObject<'py> for ContextualEntityWrapper {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         let py = obj.py(); // Obtain the Python interpreter context from `obj`
-        let py_dict: &PyDict = obj.downcast()?; // Safely cast the PyAny to PyDict
-
-        // Extract the "id" and "type_" fields explicitly
-        let id: String = match py_dict.get_item("id") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-        let type_ = create_data_type_from_dict(py_dict)?;
-
-        // Initialize an empty HashMap to hold dynamic_entity entries
-        let mut dynamic_entity_map: HashMap<String, DynamicEntity> = HashMap::new();
-
-        // Iterate over the dictionary, excluding "id" and "type" keys
-        for (key, value) in py_dict.into_iter() {
-            let key_str: String = key.extract()?; // Extract key as String
-            if key_str != "id" && key_str != "type" {
-                let dynamic_entity = convert_pyobject_to_dynamic_entity(py, value)?;
-                // Convert value to DynamicEntity and insert into the map
-                dynamic_entity_map.insert(key_str, dynamic_entity);
+        let attrs_dict;
+        let py_dict: &Bound<'py, PyDict> = match obj.downcast::<PyDict>() {
+            Ok(dict) => dict,
+            Err(_) => {
+                attrs_dict = object_attrs_as_pydict(py, obj)?;
+                &attrs_dict
             }
-        }
-
-        // Construct DataEntity, wrapping all dynamic entities in Some if not empty, else None
-        let dynamic_entity = if !dynamic_entity_map.is_empty() {
-            Some(dynamic_entity_map)
-        } else {
-            None
         };
 
-        Ok(ContextualEntityWrapper(ContextualEntity {
-            id,
-            type_,
-            dynamic_entity,
-        }))
+        let id: String = get_required(py_dict, "id")?.to_string();
+
+        let entity = (|| -> ConversionResult<ContextualEntity> {
+            let type_ = create_data_type_from_dict(py_dict)?;
+            let dynamic_entity = collect_dynamic_entities(py, py_dict, Self::KNOWN_KEYS)?;
+
+            Ok(ContextualEntity {
+                id: id.clone(),
+                type_,
+                dynamic_entity,
+            })
+        })()
+        .map_err(|e| e.at(format!("'{id}'")))?;
+
+        Ok(ContextualEntityWrapper(entity))
     }
 }
 
 pub struct RootDataEntityWrapper(pub RootDataEntity);
-impl<'source> FromPyObject<'source> for RootDataEntityWrapper {
-    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+impl RootDataEntityWrapper {
+    const KNOWN_KEYS: &'static [&'static str] = &[
+        "id",
+        "type",
+        "name",
+        "description",
+        "datePublished",
+        "license",
+    ];
+}
+impl<'py> FromPyObject<'py> for RootDataEntityWrapper {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         let py = obj.py(); // Obtain the Python interpreter context from `obj`
-        let py_dict: &PyDict = obj.downcast()?; // Safely cast the PyAny to PyDict
-
-        // Extract the "id" and "type_" fields explicitly
-        let id: String = match py_dict.get_item("id") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-        let type_ = create_data_type_from_dict(py_dict)?;
-
-        let name: String = match py_dict.get_item("name") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-
-        let description = match py_dict.get_item("description") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-
-        let license = match py_dict.get_item("license") {
-            Ok(license_obj) => convert_pyobject_to_license(py, license_obj.unwrap())?,
-            Err(e) => return Err(e),
-        };
-
-        let date_published = match py_dict.get_item("datePublished") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-
-        // Initialize an empty HashMap to hold dynamic_entity entries
-        let mut dynamic_entity_map: HashMap<String, DynamicEntity> = HashMap::new();
-
-        // Iterate over the dictionary, excluding "id" and "type" keys
-        for (key, value) in py_dict.into_iter() {
-            let key_str: String = key.extract()?; // Extract key as String
-            if key_str != "id"
-                && key_str != "type"
-                && key_str != "name"
-                && key_str != "description"
-                && key_str != "datePublished"
-                && key_str != "license"
-            {
-                let dynamic_entity = convert_pyobject_to_dynamic_entity(py, value)?;
-                // Convert value to DynamicEntity and insert into the map
-                dynamic_entity_map.insert(key_str, dynamic_entity);
+        let attrs_dict;
+        let py_dict: &Bound<'py, PyDict> = match obj.downcast::<PyDict>() {
+            Ok(dict) => dict,
+            Err(_) => {
+                attrs_dict = object_attrs_as_pydict(py, obj)?;
+                &attrs_dict
             }
-        }
-
-        // Construct DataEntity, wrapping all dynamic entities in Some if not empty, else None
-        let dynamic_entity = if !dynamic_entity_map.is_empty() {
-            Some(dynamic_entity_map)
-        } else {
-            None
         };
 
-        Ok(RootDataEntityWrapper(RootDataEntity {
-            id,
-            type_,
-            name,
-            description,
-            date_published,
-            license,
-            dynamic_entity,
-        }))
+        let id: String = get_required(py_dict, "id")?.to_string();
+
+        let entity = (|| -> ConversionResult<RootDataEntity> {
+            let type_ = create_data_type_from_dict(py_dict)?;
+            let name: String = get_required(py_dict, "name")?.to_string();
+            let description: String = get_required(py_dict, "description")?.to_string();
+            let license = convert_pyobject_to_license(py, &get_required(py_dict, "license")?)?;
+            let date_published: String = get_required(py_dict, "datePublished")?.to_string();
+            let dynamic_entity = collect_dynamic_entities(py, py_dict, Self::KNOWN_KEYS)?;
+
+            Ok(RootDataEntity {
+                id: id.clone(),
+                type_,
+                name,
+                description,
+                date_published,
+                license,
+                dynamic_entity,
+            })
+        })()
+        .map_err(|e| e.at(format!("'{id}'")))?;
+
+        Ok(RootDataEntityWrapper(entity))
     }
 }
 
 pub struct MetadataDescriptorWrapper(pub MetadataDescriptor);
-impl<'source> FromPyObject<'source> for MetadataDescriptorWrapper {
-    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+impl MetadataDescriptorWrapper {
+    const KNOWN_KEYS: &'static [&'static str] = &["id", "type", "conformsTo", "about"];
+}
+impl<'py> FromPyObject<'py> for MetadataDescriptorWrapper {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
         let py = obj.py(); // Obtain the Python interpreter context from `obj`
-        let py_dict: &PyDict = obj.downcast()?; // Safely cast the PyAny to PyDict
-
-        // Extract the "id" and "type_" fields explicitly
-        let id: String = match py_dict.get_item("id") {
-            Ok(str) => str.unwrap().to_string(),
-            Err(e) => return Err(e),
-        };
-        let type_ = create_data_type_from_dict(py_dict)?;
-
-        // This won't work because it cant pick the key TODO
-        let conforms_to = if let Ok(value) = py_dict.get_item("conformsTo") {
-            convert_dict_to_id(py, value.unwrap())?
-        } else {
-            todo!()
+        let attrs_dict;
+        let py_dict: &Bound<'py, PyDict> = match obj.downcast::<PyDict>() {
+            Ok(dict) => dict,
+            Err(_) => {
+                attrs_dict = object_attrs_as_pydict(py, obj)?;
+                &attrs_dict
+            }
         };
 
-        let about = if let Ok(about_check) = py_dict.get_item("about") {
-            convert_dict_to_id(py, about_check.unwrap())?
-        } else {
-            todo!()
-        };
+        let id: String = get_required(py_dict, "id")?.to_string();
 
-        // Initialize an empty HashMap to hold dynamic_entity entries
-        let mut dynamic_entity_map: HashMap<String, DynamicEntity> = HashMap::new();
-
-        // Iterate over the dictionary, excluding "id" and "type" keys
-        for (key, value) in py_dict.into_iter() {
-            let key_str: String = key.extract()?; // Extract key as String
-            if key_str != "id" && key_str != "type" && key_str != "conformsTo" && key_str != "about"
-            {
-                let dynamic_entity = convert_pyobject_to_dynamic_entity(py, value)?;
-                // Convert value to DynamicEntity and insert into the map
-                dynamic_entity_map.insert(key_str, dynamic_entity);
-            }
-        }
+        let entity = (|| -> ConversionResult<MetadataDescriptor> {
+            let type_ = create_data_type_from_dict(py_dict)?;
+            let conforms_to = convert_dict_to_id(py, &get_required(py_dict, "conformsTo")?)
+                .map_err(|e| e.at(".conformsTo"))?;
+            let about = convert_dict_to_id(py, &get_required(py_dict, "about")?)
+                .map_err(|e| e.at(".about"))?;
+            let dynamic_entity = collect_dynamic_entities(py, py_dict, Self::KNOWN_KEYS)?;
 
-        // Construct DataEntity, wrapping all dynamic entities in Some if not empty, else None
-        let dynamic_entity = if !dynamic_entity_map.is_empty() {
-            Some(dynamic_entity_map)
-        } else {
-            None
-        };
+            Ok(MetadataDescriptor {
+                id: id.clone(),
+                type_,
+                conforms_to,
+                about,
+                dynamic_entity,
+            })
+        })()
+        .map_err(|e| e.at(format!("'{id}'")))?;
 
-        Ok(MetadataDescriptorWrapper(MetadataDescriptor {
-            id,
-            type_,
-            conforms_to,
-            about,
-            dynamic_entity,
-        }))
+        Ok(MetadataDescriptorWrapper(entity))
     }
 }
 
-fn create_data_type_from_dict(input: &PyDict) -> PyResult<DataType> {
-    if let Ok(value) = input.get_item("type") {
-        if let Ok(s) = value.unwrap().extract::<&str>() {
-            Ok(DataType::Term(s.to_string()))
-        } else if let Ok(arr) = value.unwrap().extract::<Vec<String>>() {
-            Ok(DataType::TermArray(
-                arr.into_iter().map(String::from).collect(),
-            ))
-        } else {
-            Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-                "The 'type' key must be associated with a string or a list of strings",
-            ))
-        }
+fn create_data_type_from_dict(input: &Bound<PyDict>) -> ConversionResult<DataType> {
+    let value = get_required(input, "type")?;
+    if let Ok(s) = value.extract::<String>() {
+        Ok(DataType::Term(s))
+    } else if let Ok(arr) = value.extract::<Vec<String>>() {
+        Ok(DataType::TermArray(arr))
     } else {
-        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Dictionary must contain the 'type' key",
+        Err(ConversionError::new(
+            "the 'type' key must be associated with a string or a list of strings",
         ))
     }
 }
 
-fn convert_pyobject_to_license(py: Python, input: &PyAny) -> Result<License, PyErr> {
+fn convert_pyobject_to_license(py: Python, input: &Bound<PyAny>) -> ConversionResult<License> {
     // Attempt to extract the input as an Id using the previously defined function
     match convert_dict_to_id(py, input) {
         Ok(id) => Ok(License::Id(id)),
         Err(_) => {
             // If it fails, then try to extract a description as a fallback
             if let Ok(description) = input.extract::<String>() {
-                return Ok(License::Description(description));
+                Ok(License::Description(description))
+            } else {
+                Err(ConversionError::new("input cannot be converted to a license"))
             }
-            // If both attempts fail, return a custom PyTypeError
-            Err(PyTypeError::new_err("Input cannot be converted to License"))
         }
     }
 }
 
-fn convert_dict_to_id(_py: Python, input: &PyAny) -> PyResult<Id> {
+fn convert_dict_to_id(_py: Python, input: &Bound<PyAny>) -> ConversionResult<Id> {
     // Check if input is a single object with "id"
     // Converts to pydidct then checks id
     if let Ok(py_dict) = input.downcast::<PyDict>() {
-        if let Ok(id_str) = py_dict.get_item("id") {
+        if let Some(id_value) = py_dict.get_item("id").map_err(ConversionError::from)? {
             return Ok(Id::Id(IdValue {
-                id: id_str.unwrap().to_string(),
+                id: id_value.to_string(),
             }));
         }
     }
@@ -564,18 +613,18 @@ fn convert_dict_to_id(_py: Python, input: &PyAny) -> PyResult<Id> {
     // Check if input is a list of objects each with "id"
     if let Ok(py_list) = input.downcast::<PyList>() {
         let mut id_values: Vec<IdValue> = Vec::new();
-        for item in py_list {
-            if let Ok(py_dict) = item.downcast::<PyDict>() {
-                if let Ok(id_str) = py_dict.get_item("id") {
-                    id_values.push(IdValue {
-                        id: id_str.unwrap().to_string(),
-                    });
-                } else {
-                    return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-                        "List items must be dictionaries with an 'id' key",
-                    ));
-                }
-            }
+        for (idx, item) in py_list.iter().enumerate() {
+            let py_dict = item.downcast::<PyDict>().map_err(|_| {
+                ConversionError::new("list items must be dictionaries with an 'id' key")
+                    .at(format!("[{idx}]"))
+            })?;
+            let id_value = py_dict
+                .get_item("id")
+                .map_err(ConversionError::from)?
+                .ok_or_else(|| ConversionError::new("missing 'id' key").at(format!("[{idx}]")))?;
+            id_values.push(IdValue {
+                id: id_value.to_string(),
+            });
         }
         if !id_values.is_empty() {
             return Ok(Id::IdArray(id_values));
@@ -583,13 +632,20 @@ fn convert_dict_to_id(_py: Python, input: &PyAny) -> PyResult<Id> {
     }
 
     // If neither case matches, return an error
-    Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-        "Input must be a dictionary with an 'id' key or a list of such dictionaries",
+    Err(ConversionError::new(
+        "input must be a dictionary with an 'id' key or a list of such dictionaries",
     ))
 }
 
 // converts a PyObject to any required dynamic entity
-fn convert_pyobject_to_dynamic_entity(py: Python, obj: &PyAny) -> PyResult<DynamicEntity> {
+pub(crate) fn convert_pyobject_to_dynamic_entity(
+    py: Python,
+    obj: &Bound<PyAny>,
+) -> ConversionResult<DynamicEntity> {
+    // None - checked first since it extracts cleanly as neither a string nor a bool
+    if obj.is_none() {
+        return Ok(DynamicEntity::EntityBool(None));
+    }
     // String
     if let Ok(s) = obj.extract::<String>() {
         return Ok(DynamicEntity::EntityString(s));
@@ -622,33 +678,106 @@ fn convert_pyobject_to_dynamic_entity(py: Python, obj: &PyAny) -> PyResult<Dynam
     if let Ok(id) = convert_dict_to_id(py, obj) {
         return Ok(DynamicEntity::EntityId(id));
     }
-
-    // Check if the object is None
-    if obj.is_none() {
-        // Directly return if obj is Python None
-        return Ok(DynamicEntity::EntityString("None".to_string()));
-    }
     // Vec<DynamicEntity>
-    if let Ok(list) = obj.extract::<&PyList>() {
+    if let Ok(list) = obj.downcast::<PyList>() {
         let mut vec = Vec::new();
-        for item in list {
-            let entity = convert_pyobject_to_dynamic_entity(py, item)?;
+        for (idx, item) in list.iter().enumerate() {
+            let entity = convert_pyobject_to_dynamic_entity(py, &item)
+                .map_err(|e| e.at(format!("[{idx}]")))?;
             vec.push(entity);
         }
         return Ok(DynamicEntity::EntityVec(vec));
     }
     // HashMap<String, DynamicEntity> or Vec<HashMap<String, DynamicEntity>>
-    if let Ok(dict) = obj.extract::<&PyDict>() {
+    if let Ok(dict) = obj.downcast::<PyDict>() {
         let mut map: HashMap<String, DynamicEntity> = HashMap::new();
-        for (k, v) in dict {
-            let key: String = k.extract()?;
-            let value: DynamicEntity = convert_pyobject_to_dynamic_entity(py, v)?;
+        for (k, v) in dict.iter() {
+            let key: String = k.extract().map_err(ConversionError::from)?;
+            let value = convert_pyobject_to_dynamic_entity(py, &v)
+                .map_err(|e| e.at(format!(".{key}")))?;
             map.insert(key, value);
         }
         Ok(DynamicEntity::EntityObject(map))
     } else {
-        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Data type unavailable",
+        Err(ConversionError::new(
+            "unsupported Python type for dynamic entity conversion",
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a `DataEntity` whose dynamic properties exercise every shape the
+    /// JSON conversion needs to preserve: a nested object, an array of objects,
+    /// a float, and a null. Uses the variants `convert_pyobject_to_dynamic_entity`
+    /// itself produces on import (e.g. `EntityObject`, not `Fallback`), since
+    /// that's the canonical form a value settles into after one round trip.
+    fn round_trip_corpus() -> Vec<DataEntity> {
+        vec![
+            DataEntity {
+                id: "#entity-1".to_string(),
+                type_: DataType::Term("File".to_string()),
+                dynamic_entity: Some(HashMap::from([
+                    (
+                        "measurement".to_string(),
+                        DynamicEntity::EntityObject(HashMap::from([
+                            ("value".to_string(), DynamicEntity::Entityf64(3.5)),
+                            (
+                                "unit".to_string(),
+                                DynamicEntity::EntityString("kg".to_string()),
+                            ),
+                            (
+                                "nested".to_string(),
+                                DynamicEntity::EntityObject(HashMap::from([
+                                    ("flag".to_string(), DynamicEntity::EntityBool(Some(true))),
+                                    ("missing".to_string(), DynamicEntity::EntityBool(None)),
+                                ])),
+                            ),
+                        ])),
+                    ),
+                    (
+                        "observations".to_string(),
+                        DynamicEntity::EntityVec(vec![
+                            DynamicEntity::EntityObject(HashMap::from([(
+                                "label".to_string(),
+                                DynamicEntity::EntityString("obs-1".to_string()),
+                            )])),
+                            DynamicEntity::EntityObject(HashMap::from([(
+                                "score".to_string(),
+                                DynamicEntity::EntityBool(None),
+                            )])),
+                        ]),
+                    ),
+                    ("note".to_string(), DynamicEntity::EntityBool(None)),
+                ])),
+            },
+            DataEntity {
+                id: "#entity-2".to_string(),
+                type_: DataType::TermArray(vec!["File".to_string(), "Dataset".to_string()]),
+                dynamic_entity: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn from_pydict_of_to_pydict_round_trips() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            for entity in round_trip_corpus() {
+                let py_dict = base_entity_to_pydict(py, &entity).unwrap();
+                let wrapper: DataEntityWrapper = py_dict.extract(py).unwrap();
+                let round_tripped = wrapper.0;
+
+                assert_eq!(round_tripped.id, entity.id);
+                match (&round_tripped.type_, &entity.type_) {
+                    (DataType::Term(a), DataType::Term(b)) => assert_eq!(a, b),
+                    (DataType::TermArray(a), DataType::TermArray(b)) => assert_eq!(a, b),
+                    (a, b) => panic!("type mismatch: {a:?} vs {b:?}"),
+                }
+                assert_eq!(round_tripped.dynamic_entity, entity.dynamic_entity);
+            }
+        });
+    }
+}
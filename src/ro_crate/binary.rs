@@ -0,0 +1,86 @@
+//! Feature-gated binary serialization backends for the entity graph.
+//!
+//! Crates describing thousands of data entities are bulky and slow to parse as JSON
+//! text. These backends serialize the same `RoCrate` graph to CBOR (RFC 8949, via
+//! `ciborium`) and MessagePack (via `rmp-serde`), keeping the JSON-LD `@context`/
+//! `@graph` structure intact so a binary-serialized crate can always be re-emitted as a
+//! standard `ro-crate-metadata.json` by re-serializing through `serde_json`.
+
+use crate::ro_crate::rocrate::RoCrate;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BinarySerializationError {
+    #[cfg(feature = "cbor")]
+    #[error("CBOR encode error: {0}")]
+    CborEncode(#[from] ciborium::ser::Error<std::io::Error>),
+    #[cfg(feature = "cbor")]
+    #[error("CBOR decode error: {0}")]
+    CborDecode(#[from] ciborium::de::Error<std::io::Error>),
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack encode error: {0}")]
+    MsgPackEncode(#[from] rmp_serde::encode::Error),
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack decode error: {0}")]
+    MsgPackDecode(#[from] rmp_serde::decode::Error),
+}
+
+#[cfg(feature = "cbor")]
+impl RoCrate {
+    /// Serializes this crate's graph to CBOR (RFC 8949) bytes.
+    pub fn to_cbor_bytes(&self) -> Result<Vec<u8>, BinarySerializationError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserializes an RO-Crate graph previously written by [`RoCrate::to_cbor_bytes`].
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<RoCrate, BinarySerializationError> {
+        Ok(ciborium::from_reader(bytes)?)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl RoCrate {
+    /// Serializes this crate's graph to MessagePack bytes.
+    pub fn to_msgpack_bytes(&self) -> Result<Vec<u8>, BinarySerializationError> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+
+    /// Deserializes an RO-Crate graph previously written by [`RoCrate::to_msgpack_bytes`].
+    pub fn from_msgpack_bytes(bytes: &[u8]) -> Result<RoCrate, BinarySerializationError> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+}
+
+#[cfg(all(test, feature = "cbor"))]
+mod cbor_tests {
+    use super::*;
+
+    #[test]
+    fn test_cbor_roundtrip_preserves_context_and_graph() {
+        let rocrate = RoCrate::default();
+        let bytes = rocrate.to_cbor_bytes().unwrap();
+        let roundtripped = RoCrate::from_cbor_bytes(&bytes).unwrap();
+        assert_eq!(
+            rocrate.to_canonical_string().unwrap(),
+            roundtripped.to_canonical_string().unwrap()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "msgpack"))]
+mod msgpack_tests {
+    use super::*;
+
+    #[test]
+    fn test_msgpack_roundtrip_preserves_context_and_graph() {
+        let rocrate = RoCrate::default();
+        let bytes = rocrate.to_msgpack_bytes().unwrap();
+        let roundtripped = RoCrate::from_msgpack_bytes(&bytes).unwrap();
+        assert_eq!(
+            rocrate.to_canonical_string().unwrap(),
+            roundtripped.to_canonical_string().unwrap()
+        );
+    }
+}
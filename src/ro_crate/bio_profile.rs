@@ -0,0 +1,220 @@
+//! Optional bioinformatics data-entity profile: FASTA/GFF metadata extraction.
+//!
+//! RO-Crate is heavily used for packaging genomics datasets, but the rest of this crate
+//! treats sequence files as opaque data entities. This module recognizes FASTA and GFF
+//! files and derives queryable metadata from them without requiring the consumer to
+//! reopen the raw file: for FASTA, the sequence count, per-sequence identifiers, and an
+//! inferred molecule type (DNA vs RNA); for GFF, the set of annotated feature types and
+//! their coordinate ranges. Both parsers stream the file line by line rather than
+//! loading it fully, and tolerate mixed-case bases and ambiguity codes (`N`, etc.).
+
+use crate::ro_crate::constraints::DataType;
+use crate::ro_crate::data_entity::DataEntity;
+use crate::ro_crate::modify::{DynamicEntity, DynamicEntityManipulation};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+/// Whether a FASTA file's sequences look like DNA or RNA, inferred from the alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoleculeType {
+    Dna,
+    Rna,
+}
+
+impl MoleculeType {
+    fn as_str(self) -> &'static str {
+        match self {
+            MoleculeType::Dna => "DNA",
+            MoleculeType::Rna => "RNA",
+        }
+    }
+}
+
+/// Derived metadata for a FASTA file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FastaSummary {
+    pub sequence_count: usize,
+    pub sequence_ids: Vec<String>,
+    pub molecule_type: Option<MoleculeType>,
+}
+
+/// Streams a FASTA file line by line, recording header lines and inferring the
+/// molecule type from the bases of every sequence body. Tolerates lowercase bases
+/// and ambiguity codes (`N`, `R`, `Y`, etc.) - only the presence of `U` vs `T` is used
+/// to distinguish RNA from DNA.
+pub fn parse_fasta<R: BufRead>(reader: R) -> io::Result<FastaSummary> {
+    let mut summary = FastaSummary::default();
+    let mut saw_t = false;
+    let mut saw_u = false;
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim_end();
+        if let Some(header) = line.strip_prefix('>') {
+            summary.sequence_count += 1;
+            let id = header.split_whitespace().next().unwrap_or("").to_string();
+            summary.sequence_ids.push(id);
+        } else if !line.is_empty() {
+            for base in line.chars() {
+                match base.to_ascii_uppercase() {
+                    'T' => saw_t = true,
+                    'U' => saw_u = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    summary.molecule_type = if saw_u && !saw_t {
+        Some(MoleculeType::Rna)
+    } else if summary.sequence_count > 0 {
+        Some(MoleculeType::Dna)
+    } else {
+        None
+    };
+
+    Ok(summary)
+}
+
+/// A single annotated feature's type and coordinate range, as read from a GFF record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GffFeatureRange {
+    pub feature_type: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Derived metadata for a GFF file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GffSummary {
+    pub feature_types: Vec<String>,
+    pub ranges: Vec<GffFeatureRange>,
+}
+
+/// Streams a GFF file line by line, recording each record's feature type (column 3)
+/// and start/end coordinates (columns 4/5). Comment (`#`) and blank lines are skipped;
+/// malformed records are skipped rather than aborting the whole file.
+pub fn parse_gff<R: BufRead>(reader: R) -> io::Result<GffSummary> {
+    let mut summary = GffSummary::default();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() < 5 {
+            continue;
+        }
+
+        let feature_type = columns[2].to_string();
+        let (Ok(start), Ok(end)) = (columns[3].parse::<u64>(), columns[4].parse::<u64>()) else {
+            continue;
+        };
+
+        if !summary.feature_types.contains(&feature_type) {
+            summary.feature_types.push(feature_type.clone());
+        }
+        summary.ranges.push(GffFeatureRange {
+            feature_type,
+            start,
+            end,
+        });
+    }
+
+    Ok(summary)
+}
+
+/// Writes a `FastaSummary` onto `entity` as additional dynamic properties
+/// (`sequenceCount`, `sequenceIds`, `moleculeType`), ready to be serialized through the
+/// normal crate-writing path alongside the entity's other fields.
+pub fn enrich_data_entity_with_fasta(entity: &mut DataEntity, summary: &FastaSummary) {
+    let mut properties = HashMap::new();
+    properties.insert(
+        "sequenceCount".to_string(),
+        DynamicEntity::EntityString(summary.sequence_count.to_string()),
+    );
+    properties.insert(
+        "sequenceIds".to_string(),
+        DynamicEntity::EntityString(summary.sequence_ids.join(",")),
+    );
+    if let Some(molecule_type) = summary.molecule_type {
+        properties.insert(
+            "moleculeType".to_string(),
+            DynamicEntity::EntityString(molecule_type.as_str().to_string()),
+        );
+    }
+    entity.add_dynamic_entity_field(properties);
+}
+
+/// Writes a `GffSummary` onto `entity` as additional dynamic properties
+/// (`featureTypes`, `featureRanges`).
+pub fn enrich_data_entity_with_gff(entity: &mut DataEntity, summary: &GffSummary) {
+    let mut properties = HashMap::new();
+    properties.insert(
+        "featureTypes".to_string(),
+        DynamicEntity::EntityString(summary.feature_types.join(",")),
+    );
+    let ranges = summary
+        .ranges
+        .iter()
+        .map(|r| format!("{}:{}-{}", r.feature_type, r.start, r.end))
+        .collect::<Vec<_>>()
+        .join(",");
+    properties.insert(
+        "featureRanges".to_string(),
+        DynamicEntity::EntityString(ranges),
+    );
+    entity.add_dynamic_entity_field(properties);
+}
+
+/// Recognizes a FASTA file from its data entity's `@type` plus a conventional file
+/// extension check on its `@id`, since there is no dedicated RO-Crate profile term for
+/// it.
+pub fn looks_like_fasta(entity: &DataEntity) -> bool {
+    matches!(&entity.type_, DataType::Term(t) if t == "File")
+        && ["fa", "fasta", "fna", "faa"]
+            .iter()
+            .any(|ext| entity.id.ends_with(&format!(".{ext}")))
+}
+
+/// Recognizes a GFF file the same way `looks_like_fasta` recognizes a FASTA file.
+pub fn looks_like_gff(entity: &DataEntity) -> bool {
+    matches!(&entity.type_, DataType::Term(t) if t == "File")
+        && ["gff", "gff3"]
+            .iter()
+            .any(|ext| entity.id.ends_with(&format!(".{ext}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_fasta_detects_dna() {
+        let fasta = ">seq1 description\nACGTN\n>seq2\nacgtn\n";
+        let summary = parse_fasta(Cursor::new(fasta)).unwrap();
+        assert_eq!(summary.sequence_count, 2);
+        assert_eq!(summary.sequence_ids, vec!["seq1", "seq2"]);
+        assert_eq!(summary.molecule_type, Some(MoleculeType::Dna));
+    }
+
+    #[test]
+    fn test_parse_fasta_detects_rna() {
+        let fasta = ">seq1\nACGUN\n";
+        let summary = parse_fasta(Cursor::new(fasta)).unwrap();
+        assert_eq!(summary.molecule_type, Some(MoleculeType::Rna));
+    }
+
+    #[test]
+    fn test_parse_gff_collects_feature_types_and_ranges() {
+        let gff = "##gff-version 3\nchr1\tsrc\tgene\t100\t200\t.\t+\t.\tID=gene1\nchr1\tsrc\texon\t110\t150\t.\t+\t.\tID=exon1\n";
+        let summary = parse_gff(Cursor::new(gff)).unwrap();
+        assert_eq!(summary.feature_types, vec!["gene", "exon"]);
+        assert_eq!(summary.ranges.len(), 2);
+        assert_eq!(summary.ranges[0].start, 100);
+        assert_eq!(summary.ranges[0].end, 200);
+    }
+}
@@ -0,0 +1,117 @@
+//! Canonical (deterministic) JSON-LD serialization for RO-Crates.
+//!
+//! `serde_json::to_string_pretty` gives no guarantee of stable key ordering or
+//! byte-identical output across runs, which matters for anyone who wants to sign an
+//! RO-Crate's `ro-crate-metadata.json` or content-address it by hash. This module walks
+//! the crate's `serde_json::Value` representation and emits a fixed byte sequence:
+//! object keys sorted lexicographically, compact separators, and normalized number/
+//! string formatting, so two independently built but structurally equal crates always
+//! serialize byte-for-byte identically.
+
+use crate::ro_crate::rocrate::RoCrate;
+use serde_json::Value;
+use std::io::{self, Write};
+
+/// Serializes `rocrate` to its canonical JSON-LD string.
+pub fn to_canonical_string(rocrate: &RoCrate) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(rocrate)?;
+    let mut out = String::new();
+    write_canonical_value(&value, &mut out);
+    Ok(out)
+}
+
+/// Writes `rocrate`'s canonical form directly to `writer`.
+pub fn write_canonical<W: Write>(rocrate: &RoCrate, writer: &mut W) -> io::Result<()> {
+    let value = serde_json::to_value(rocrate).map_err(io::Error::other)?;
+    let mut out = String::new();
+    write_canonical_value(&value, &mut out);
+    writer.write_all(out.as_bytes())
+}
+
+fn write_canonical_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&canonical_number(n)),
+        Value::String(s) => write_canonical_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            out.push('{');
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_string(key, out);
+                out.push(':');
+                write_canonical_value(&map[key.as_str()], out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Normalizes number formatting: integers print without a decimal point or exponent.
+fn canonical_number(n: &serde_json::Number) -> String {
+    if let Some(i) = n.as_i64() {
+        i.to_string()
+    } else if let Some(u) = n.as_u64() {
+        u.to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+/// Escapes a string the same fixed way every time: only the characters JSON requires
+/// are escaped, with control characters outside the named escapes written as `\uXXXX`.
+fn write_canonical_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ro_crate::rocrate::RoCrate;
+
+    fn reference_crate() -> RoCrate {
+        RoCrate::default()
+    }
+
+    #[test]
+    fn test_canonical_output_is_stable_across_independent_builds() {
+        let a = to_canonical_string(&reference_crate()).unwrap();
+        let b = to_canonical_string(&reference_crate()).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_canonical_output_sorts_keys_and_is_compact() {
+        let canonical = to_canonical_string(&reference_crate()).unwrap();
+        assert!(!canonical.contains('\n'));
+        assert!(!canonical.contains(", "));
+        // "@context" sorts before "@graph" lexicographically.
+        assert!(canonical.find("@context").unwrap() < canonical.find("@graph").unwrap());
+    }
+}
@@ -8,15 +8,42 @@
 //
 // If storage becomes an issue - then that's a good thing and this whole project
 // is succeeding
-use crate::ro_crate::context::{ContextItem, RoCrateContext};
-use crate::ro_crate::rocrate::RoCrate;
+//
+// For graph rows the "type" column doesn't hold the entity's `@type` (that's just
+// another row, keyed "@type", alongside the rest) - it holds which `GraphVector`
+// variant the row's `@id` belongs to ("DataEntity", "RootDataEntity", ...), since
+// that's what `from_df` needs to rebuild the right struct per id.
+use crate::ro_crate::constraints::DataType;
+use crate::ro_crate::contextual_entity::ContextualEntity;
+use crate::ro_crate::data_entity::DataEntity;
+use crate::ro_crate::metadata_descriptor::MetadataDescriptor;
+use crate::ro_crate::modify::DynamicEntity;
+use crate::ro_crate::rocrate::{ContextItem, RoCrate, RoCrateContext};
+use crate::ro_crate::root::RootDataEntity;
 use polars::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
 
 use super::graph_vector::GraphVector;
 
-pub fn to_df(rocrate: &RoCrate) -> DataFrame {
+/// Errors converting a `RoCrate` to/from the long-format `DataFrame`.
+#[derive(Error, Debug)]
+pub enum ConvertError {
+    #[error("crate context has no urn:uuid entry; call `add_urn_uuid()` on the crate before converting it to a DataFrame")]
+    MissingUrnUuid,
+    #[error("polars error: {0}")]
+    Polars(#[from] PolarsError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub fn to_df(rocrate: &RoCrate) -> Result<DataFrame, ConvertError> {
     // Get uuid
-    let uuid = rocrate.context.get_urn_uuid().unwrap();
+    let uuid = rocrate
+        .context
+        .get_urn_uuid()
+        .ok_or(ConvertError::MissingUrnUuid)?;
 
     // Build the context
     let mut crate_frame = CrateFrame {
@@ -40,10 +67,104 @@ pub fn to_df(rocrate: &RoCrate) -> DataFrame {
         Series::new("type".into(), crate_frame.etype.clone()).into(),
         Series::new("key".into(), crate_frame.key.clone()).into(),
         Series::new("value".into(), crate_frame.value.clone()).into(),
-    ])
-    .unwrap();
+    ])?;
     // Iterate through the graph
-    df
+    Ok(df)
+}
+
+/// Writes a crate straight to a Parquet file, skipping the intermediate `DataFrame`
+/// the caller would otherwise have to hold onto.
+pub fn write_parquet(rocrate: &RoCrate, path: &Path) -> Result<(), ConvertError> {
+    let mut df = to_df(rocrate)?;
+    let file = std::fs::File::create(path)?;
+    ParquetWriter::new(file).finish(&mut df)?;
+    Ok(())
+}
+
+/// Reads a Parquet file written by [`write_parquet`] back into a `RoCrate`.
+pub fn read_parquet(path: &Path) -> Result<RoCrate, ConvertError> {
+    let file = std::fs::File::open(path)?;
+    let df = ParquetReader::new(file).finish()?;
+    Ok(from_df(&df))
+}
+
+// Query layer: once a crate (or a corpus of crates) is in this long format,
+// there's no need to go back through `from_df`/JSON just to ask a question of
+// it - these run straight off the dataframe with polars' lazy filters.
+
+/// All `@id`s whose `@type` row matches `type_name` exactly.
+pub fn entities_of_type(df: &DataFrame, type_name: &str) -> PolarsResult<Vec<String>> {
+    let matches = df
+        .clone()
+        .lazy()
+        .filter(col("type").neq(lit("@context")))
+        .filter(col("key").eq(lit("@type")))
+        .filter(col("value").eq(lit(type_name)))
+        .select([col("id")])
+        .collect()?;
+
+    Ok(matches
+        .column("id")?
+        .str()?
+        .into_no_null_iter()
+        .map(String::from)
+        .collect())
+}
+
+/// Every value recorded for `key` on the entity `id` (more than one if the
+/// property was flattened into indexed `key[0]`, `key[1]`, ... rows).
+pub fn values_for(df: &DataFrame, id: &str, key: &str) -> PolarsResult<Vec<String>> {
+    let matches = df
+        .clone()
+        .lazy()
+        .filter(col("id").eq(lit(id)))
+        .filter(
+            col("key")
+                .eq(lit(key))
+                .or(col("key").str().starts_with(lit(format!("{key}[")))),
+        )
+        .select([col("value")])
+        .collect()?;
+
+    Ok(matches
+        .column("value")?
+        .str()?
+        .into_no_null_iter()
+        .map(String::from)
+        .collect())
+}
+
+/// Filters the frame down to the `(id, type, key, value)` rows matching every
+/// `(key, value)` predicate - an `id` only survives if each predicate matches at
+/// least one of its rows.
+pub fn select(df: &DataFrame, predicates: &[(&str, &str)]) -> PolarsResult<DataFrame> {
+    let mut matching_ids = df.clone().lazy();
+    for (key, value) in predicates {
+        let ids = df
+            .clone()
+            .lazy()
+            .filter(col("key").eq(lit(*key)).and(col("value").eq(lit(*value))))
+            .select([col("id")])
+            .collect()?;
+        let ids: Vec<String> = ids
+            .column("id")?
+            .str()?
+            .into_no_null_iter()
+            .map(String::from)
+            .collect();
+        matching_ids = matching_ids.filter(col("id").is_in(lit(Series::new("".into(), ids))));
+    }
+    matching_ids.collect()
+}
+
+/// Vertically stacks the `DataFrame`s of several crates into one corpus-wide
+/// table, kept distinguishable by their `uuid` column.
+pub fn join_crates(crates: &[DataFrame]) -> PolarsResult<DataFrame> {
+    let mut lazy_frames = Vec::with_capacity(crates.len());
+    for df in crates {
+        lazy_frames.push(df.clone().lazy());
+    }
+    concat(lazy_frames, UnionArgs::default())?.collect()
 }
 
 struct CrateFrame {
@@ -98,58 +219,289 @@ fn frame_graph(crate_frame: &mut CrateFrame, rocrate: &RoCrate) {
         let entity = rocrate.get_entity(id).unwrap();
         match entity {
             GraphVector::MetadataDescriptor(data) => {
-                let d_id = &data.id;
-                let d_type = &data.type_;
-
-                let d_conforms = &data.conforms_to;
-                let about = &data.about;
-
+                crate_frame.push_data(
+                    &data.id,
+                    "MetadataDescriptor",
+                    "@type",
+                    &data_type_to_value(&data.type_),
+                );
+                if let Some(conforms_to) = &data.conforms_to {
+                    crate_frame.push_data(&data.id, "MetadataDescriptor", "conformsTo", conforms_to);
+                }
+                if let Some(about) = &data.about {
+                    crate_frame.push_data(&data.id, "MetadataDescriptor", "about", about);
+                }
                 if let Some(dynamic_entity) = &data.dynamic_entity {
-                    for (key, value) in dynamic_entity {
-                        println!("dynamic entity: {}:{}", key, value);
-                    }
+                    push_dynamic_entity(crate_frame, &data.id, "MetadataDescriptor", dynamic_entity);
                 }
             }
             GraphVector::RootDataEntity(data) => {
-                let d_id = &data.id;
-                let d_type = &data.type_;
-
-                let d_name = &data.name;
-                let d_descrption = &data.description;
-                let d_date_published = &data.date_published;
-                let d_license = &data.license;
-
+                crate_frame.push_data(
+                    &data.id,
+                    "RootDataEntity",
+                    "@type",
+                    &data_type_to_value(&data.type_),
+                );
+                if let Some(name) = &data.name {
+                    crate_frame.push_data(&data.id, "RootDataEntity", "name", name);
+                }
+                if let Some(description) = &data.description {
+                    crate_frame.push_data(&data.id, "RootDataEntity", "description", description);
+                }
+                if let Some(date_published) = &data.date_published {
+                    crate_frame.push_data(&data.id, "RootDataEntity", "datePublished", date_published);
+                }
+                if let Some(license) = &data.license {
+                    crate_frame.push_data(&data.id, "RootDataEntity", "license", license);
+                }
                 if let Some(dynamic_entity) = &data.dynamic_entity {
-                    for (key, value) in dynamic_entity {
-                        println!("dynamic entity: {}:{}", key, value);
-                    }
+                    push_dynamic_entity(crate_frame, &data.id, "RootDataEntity", dynamic_entity);
                 }
             }
             GraphVector::ContextualEntity(data) => {
-                let d_id = &data.id;
-                let d_type = &data.type_;
+                crate_frame.push_data(
+                    &data.id,
+                    "ContextualEntity",
+                    "@type",
+                    &data_type_to_value(&data.type_),
+                );
                 if let Some(dynamic_entity) = &data.dynamic_entity {
-                    for (key, value) in dynamic_entity {
-                        println!("dynamic entity: {}:{}", key, value);
-                    }
+                    push_dynamic_entity(crate_frame, &data.id, "ContextualEntity", dynamic_entity);
                 }
             }
             GraphVector::DataEntity(data) => {
-                let d_id = &data.id;
-                let d_type = &data.type_;
+                crate_frame.push_data(
+                    &data.id,
+                    "DataEntity",
+                    "@type",
+                    &data_type_to_value(&data.type_),
+                );
                 if let Some(dynamic_entity) = &data.dynamic_entity {
-                    for (key, value) in dynamic_entity {
-                        println!("dynamic entity: {}:{}", key, value);
-                    }
+                    push_dynamic_entity(crate_frame, &data.id, "DataEntity", dynamic_entity);
                 }
             }
         }
     }
 }
 
+fn data_type_to_value(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Term(term) => term.clone(),
+        DataType::TermArray(terms) => terms.join(","),
+    }
+}
+
+fn data_type_from_value(value: &str) -> DataType {
+    if value.contains(',') {
+        DataType::TermArray(value.split(',').map(String::from).collect())
+    } else {
+        DataType::Term(value.to_string())
+    }
+}
+
+/// Flattens a `dynamic_entity` map into `(id, type, key, value)` rows.
+///
+/// Each value is flattened through its string form (`to_string()`) rather than walked
+/// as a nested object, and comes back as `EntityString` - the best this does for a multi-valued property is
+/// the comma-joined convention the rest of the crate already uses (see
+/// `bio_profile.rs`'s `sequenceIds`/`featureTypes`), split back out into indexed
+/// `key[0]`, `key[1]`, ... rows so each value is independently queryable.
+fn push_dynamic_entity(
+    crate_frame: &mut CrateFrame,
+    id: &str,
+    etype: &str,
+    dynamic_entity: &HashMap<String, DynamicEntity>,
+) {
+    for (key, value) in dynamic_entity {
+        let value = value.to_string();
+        let segments: Vec<&str> = value.split(',').collect();
+        if segments.len() > 1 {
+            for (index, segment) in segments.iter().enumerate() {
+                crate_frame.push_data(id, etype, &format!("{key}[{index}]"), segment);
+            }
+        } else {
+            crate_frame.push_data(id, etype, key, &value);
+        }
+    }
+}
+
+/// Rebuilds a `RoCrate` from the long-format table produced by [`to_df`].
+fn from_df(df: &DataFrame) -> RoCrate {
+    let uuid_col = df.column("uuid").unwrap().str().unwrap();
+    let id_col = df.column("id").unwrap().str().unwrap();
+    let type_col = df.column("type").unwrap().str().unwrap();
+    let key_col = df.column("key").unwrap().str().unwrap();
+    let value_col = df.column("value").unwrap().str().unwrap();
+
+    let mut context_rows: Vec<(String, String)> = Vec::new();
+    let mut entities: HashMap<String, (String, Vec<(String, String)>)> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut uuid = String::new();
+
+    for i in 0..df.height() {
+        let row_uuid = uuid_col.get(i).unwrap_or_default();
+        let row_id = id_col.get(i).unwrap_or_default();
+        let row_type = type_col.get(i).unwrap_or_default();
+        let row_key = key_col.get(i).unwrap_or_default();
+        let row_value = value_col.get(i).unwrap_or_default();
+
+        if uuid.is_empty() {
+            uuid = row_uuid.to_string();
+        }
+
+        if row_type == "@context" {
+            context_rows.push((row_key.to_string(), row_value.to_string()));
+            continue;
+        }
+
+        let entry = entities.entry(row_id.to_string()).or_insert_with(|| {
+            order.push(row_id.to_string());
+            (row_type.to_string(), Vec::new())
+        });
+        entry.1.push((row_key.to_string(), row_value.to_string()));
+    }
+
+    // `uuid` only distinguishes rows when several crates share one frame (see
+    // `join_crates`); the crate's own `@context` content comes back entirely from
+    // `context_rows`, so there's nothing further to do with it here.
+    let _ = uuid;
+    let mut rocrate = RoCrate::new(rebuild_context(&context_rows), Vec::new());
+    for id in order {
+        let (variant, rows) = entities.remove(&id).unwrap();
+        rocrate.graph.push(rebuild_entity(&id, &variant, &rows));
+    }
+    rocrate
+}
+
+/// Rebuilds `@context` from its flattened rows.
+///
+/// This is lossy in one corner: a single "ro-crate"-keyed row can't be told apart
+/// from an `ExtendedContext` that happened to contain exactly one reference item,
+/// so it's always reconstructed as the simpler `ReferenceContext`. Anything else
+/// comes back as a single embedded map.
+fn rebuild_context(rows: &[(String, String)]) -> RoCrateContext {
+    if let [(key, value)] = rows {
+        if key == "ro-crate" {
+            return RoCrateContext::ReferenceContext(value.clone());
+        }
+    }
+
+    let mut map: HashMap<String, String> = HashMap::new();
+    for (key, value) in rows {
+        map.insert(key.clone(), value.clone());
+    }
+    RoCrateContext::ExtendedContext(vec![ContextItem::EmbeddedContext(map)])
+}
+
+const TYPED_FIELD_KEYS: [&str; 6] = [
+    "conformsTo",
+    "about",
+    "name",
+    "description",
+    "datePublished",
+    "license",
+];
+
+fn rebuild_entity(id: &str, variant: &str, rows: &[(String, String)]) -> GraphVector {
+    let mut type_value = String::new();
+    let mut typed: HashMap<&str, String> = HashMap::new();
+    let mut dynamic_rows: HashMap<String, DynamicEntity> = HashMap::new();
+    let mut flattened: Vec<(&String, &String)> = Vec::new();
+
+    for (key, value) in rows {
+        if key == "@type" {
+            type_value = value.clone();
+        } else if TYPED_FIELD_KEYS.contains(&key.as_str()) {
+            typed.insert(key.as_str(), value.clone());
+        } else {
+            flattened.push((key, value));
+        }
+    }
+    if !flattened.is_empty() {
+        dynamic_rows = rebuild_dynamic_entity(&flattened);
+    }
+    let dynamic_entity = if dynamic_rows.is_empty() {
+        None
+    } else {
+        Some(dynamic_rows)
+    };
+    let type_ = data_type_from_value(&type_value);
+
+    match variant {
+        "MetadataDescriptor" => GraphVector::MetadataDescriptor(MetadataDescriptor {
+            id: id.to_string(),
+            type_,
+            conforms_to: typed.get("conformsTo").cloned(),
+            about: typed.get("about").cloned(),
+            dynamic_entity,
+        }),
+        "RootDataEntity" => GraphVector::RootDataEntity(RootDataEntity {
+            id: id.to_string(),
+            type_,
+            name: typed.get("name").cloned(),
+            description: typed.get("description").cloned(),
+            date_published: typed.get("datePublished").cloned(),
+            license: typed.get("license").cloned(),
+            dynamic_entity,
+        }),
+        "ContextualEntity" => GraphVector::ContextualEntity(ContextualEntity {
+            id: id.to_string(),
+            type_,
+            dynamic_entity,
+        }),
+        _ => GraphVector::DataEntity(DataEntity {
+            id: id.to_string(),
+            type_,
+            dynamic_entity,
+        }),
+    }
+}
+
+/// Reverses [`push_dynamic_entity`]'s comma/index-segment flattening.
+fn rebuild_dynamic_entity(rows: &[(&String, &String)]) -> HashMap<String, DynamicEntity> {
+    let mut grouped: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+
+    for (key, value) in rows {
+        match split_index_suffix(key) {
+            Some((base, index)) => grouped
+                .entry(base)
+                .or_default()
+                .push((index, value.to_string())),
+            None => grouped
+                .entry((*key).clone())
+                .or_default()
+                .push((0, value.to_string())),
+        }
+    }
+
+    grouped
+        .into_iter()
+        .map(|(key, mut segments)| {
+            segments.sort_by_key(|(index, _)| *index);
+            let joined = segments
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>()
+                .join(",");
+            (key, DynamicEntity::EntityString(joined))
+        })
+        .collect()
+}
+
+/// Splits a trailing `[<digits>]` index segment off a flattened key, e.g.
+/// `"keyword[1]"` -> `("keyword", 1)`.
+fn split_index_suffix(key: &str) -> Option<(String, usize)> {
+    let open = key.rfind('[')?;
+    if !key.ends_with(']') {
+        return None;
+    }
+    let index = key[open + 1..key.len() - 1].parse::<usize>().ok()?;
+    Some((key[..open].to_string(), index))
+}
+
 #[cfg(test)]
 mod write_crate_tests {
-    use crate::ro_crate::convert::to_df;
+    use crate::ro_crate::convert::{entities_of_type, from_df, join_crates, to_df, values_for};
     use crate::ro_crate::read::read_crate;
     use std::path::Path;
     use std::path::PathBuf;
@@ -164,6 +516,53 @@ mod write_crate_tests {
         let mut rocrate = read_crate(&path, 0).unwrap();
         rocrate.context.add_urn_uuid();
         println!("Crate: {:?}", rocrate);
-        let df = to_df(&rocrate);
+        let df = to_df(&rocrate).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_through_parquet_frame() {
+        let path = fixture_path("_ro-crate-metadata-dynamic.json");
+        let mut rocrate = read_crate(&path, 0).unwrap();
+        rocrate.context.add_urn_uuid();
+
+        let df = to_df(&rocrate).unwrap();
+        let rebuilt = from_df(&df);
+
+        assert_eq!(rocrate.get_all_ids(), rebuilt.get_all_ids());
+        for id in rocrate.get_all_ids() {
+            assert_eq!(
+                format!("{:?}", rocrate.get_entity(id)),
+                format!("{:?}", rebuilt.get_entity(id))
+            );
+        }
+    }
+
+    #[test]
+    fn test_entities_of_type_and_values_for() {
+        let path = fixture_path("_ro-crate-metadata-dynamic.json");
+        let mut rocrate = read_crate(&path, 0).unwrap();
+        rocrate.context.add_urn_uuid();
+        let df = to_df(&rocrate).unwrap();
+
+        let ids = entities_of_type(&df, "Dataset").unwrap();
+        for id in &ids {
+            let type_values = values_for(&df, id, "@type").unwrap();
+            assert!(type_values.iter().any(|value| value == "Dataset"));
+        }
+    }
+
+    #[test]
+    fn test_join_crates_keeps_both_uuids() {
+        let path = fixture_path("_ro-crate-metadata-dynamic.json");
+        let mut first = read_crate(&path, 0).unwrap();
+        first.context.add_urn_uuid();
+        let mut second = read_crate(&path, 0).unwrap();
+        second.context.add_urn_uuid();
+
+        let joined = join_crates(&[to_df(&first).unwrap(), to_df(&second).unwrap()]).unwrap();
+        assert_eq!(
+            joined.height(),
+            to_df(&first).unwrap().height() + to_df(&second).unwrap().height()
+        );
     }
 }
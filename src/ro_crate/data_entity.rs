@@ -6,12 +6,32 @@
 use crate::ro_crate::constraints::*;
 use crate::ro_crate::modify::*;
 use serde::{
-    de::{self, MapAccess, Visitor},
+    de::{self, DeserializeSeed, MapAccess, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use std::collections::HashMap;
 use std::fmt;
 
+/// Helper for deserializing `@type` as either a single term or an array of terms,
+/// independent of however `DataType`'s own `Deserialize` impl handles it - real
+/// crates frequently give `@type` as `["File", "SoftwareSourceCode"]` rather than
+/// a bare string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TypeValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl From<TypeValue> for DataType {
+    fn from(value: TypeValue) -> Self {
+        match value {
+            TypeValue::Single(term) => DataType::Term(term),
+            TypeValue::Multiple(terms) => DataType::TermArray(terms),
+        }
+    }
+}
+
 /// Represents a data entity with an identifier, type, and dynamic properties.
 ///
 /// `DataEntity` is designed to encapsulate an entity with a unique identifier (`id`),
@@ -120,7 +140,7 @@ impl<'de> Deserialize<'de> for DataEntity {
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
                         "@id" => id = Some(map.next_value()?),
-                        "@type" => type_ = Some(map.next_value()?),
+                        "@type" => type_ = Some(DataType::from(map.next_value::<TypeValue>()?)),
                         _ => {
                             let value: DynamicEntity = map.next_value()?;
                             dynamic_entity.insert(key, value);
@@ -143,6 +163,86 @@ impl<'de> Deserialize<'de> for DataEntity {
     }
 }
 
+/// A recoverable issue found while lenient-deserializing a `DataEntity` - the
+/// entity was still loaded, just with something defaulted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataEntityWarning {
+    pub id: String,
+    pub message: String,
+}
+
+/// `DeserializeSeed` for a `DataEntity` that, unlike the plain `Deserialize` impl
+/// above, doesn't fail the whole crate load over one malformed entity: a missing
+/// `@type` is recorded as a [`DataEntityWarning`] and defaulted to an empty term
+/// instead of erroring. `@id` is still required - without it there's no entity to
+/// attach the warning to, or for anything else in the crate to reference.
+///
+/// Collect one of these per entity and thread the accumulated warnings back to
+/// the caller of `read_crate` once its lenient-mode entry point exists.
+pub struct LenientDataEntity<'a> {
+    pub warnings: &'a mut Vec<DataEntityWarning>,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for LenientDataEntity<'a> {
+    type Value = DataEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LenientVisitor<'a> {
+            warnings: &'a mut Vec<DataEntityWarning>,
+        }
+
+        impl<'de, 'a> Visitor<'de> for LenientVisitor<'a> {
+            type Value = DataEntity;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map representing a DataEntity")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<DataEntity, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut id = None;
+                let mut type_ = None;
+                let mut dynamic_entity: HashMap<String, DynamicEntity> = HashMap::new();
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "@id" => id = Some(map.next_value()?),
+                        "@type" => type_ = Some(DataType::from(map.next_value::<TypeValue>()?)),
+                        _ => {
+                            let value: DynamicEntity = map.next_value()?;
+                            dynamic_entity.insert(key, value);
+                        }
+                    }
+                }
+
+                let id: String = id.ok_or_else(|| de::Error::missing_field("@id"))?;
+                let type_ = type_.unwrap_or_else(|| {
+                    self.warnings.push(DataEntityWarning {
+                        id: id.clone(),
+                        message: "missing @type, defaulted to an empty term".to_string(),
+                    });
+                    DataType::Term(String::new())
+                });
+
+                Ok(DataEntity {
+                    id,
+                    type_,
+                    dynamic_entity: Some(dynamic_entity),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(LenientVisitor {
+            warnings: self.warnings,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,4 +344,53 @@ mod tests {
         let result: Result<DataEntity, _> = serde_json::from_str(json_data);
         assert!(result.is_err()); // Expecting an error due to missing @type field
     }
+
+    #[test]
+    fn test_deserialization_with_array_type() {
+        let json_data = r#"
+            {
+                "@id": "entity_id",
+                "@type": ["File", "SoftwareSourceCode"]
+            }
+        "#;
+        let deserialized: DataEntity = serde_json::from_str(json_data).unwrap();
+        assert!(matches!(
+            deserialized.type_,
+            DataType::TermArray(ref terms) if terms == &vec!["File".to_string(), "SoftwareSourceCode".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_lenient_deserialize_defaults_missing_type_and_warns() {
+        let json_data = r#"
+            {
+                "@id": "entity_id"
+            }
+        "#;
+        let mut warnings = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_str(json_data);
+        let entity = LenientDataEntity {
+            warnings: &mut warnings,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        assert_eq!(entity.id, "entity_id");
+        assert!(matches!(entity.type_, DataType::Term(ref t) if t.is_empty()));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, "entity_id");
+    }
+
+    #[test]
+    fn test_lenient_deserialize_still_requires_id() {
+        let json_data = r#"{ "@type": "File" }"#;
+        let mut warnings = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_str(json_data);
+        let result = LenientDataEntity {
+            warnings: &mut warnings,
+        }
+        .deserialize(&mut deserializer);
+
+        assert!(result.is_err());
+    }
 }
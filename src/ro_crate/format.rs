@@ -0,0 +1,138 @@
+//! Pluggable crate-format registry.
+//!
+//! JSON (via the existing `CustomSerialize`/`Deserialize` path), RON and the Parquet
+//! frame (`convert::to_df`/`from_df`) all go through the same [`CrateFormat`]
+//! trait, so [`FormatRegistry::read`]/[`FormatRegistry::write`] can dispatch on a
+//! path's extension and third parties can register another encoding
+//! without touching `GraphVector` or any of the entity types.
+
+use crate::ro_crate::convert::{read_parquet, write_parquet, ConvertError};
+use crate::ro_crate::read::{read_crate, CrateReadError};
+use crate::ro_crate::rocrate::RoCrate;
+use crate::ro_crate::write::{from_ron, to_ron, write_crate, WriteError};
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FormatError {
+    #[error("read error: {0}")]
+    Read(#[from] CrateReadError),
+    #[error("write error: {0}")]
+    Write(#[from] WriteError),
+    #[error("parquet error: {0}")]
+    Parquet(#[from] ConvertError),
+    #[error("no format registered for extension `{0}`")]
+    UnknownExtension(String),
+}
+
+/// A single crate encoding: how to turn it into bytes on disk and back.
+pub trait CrateFormat {
+    fn read(&self, path: &Path) -> Result<RoCrate, FormatError>;
+    fn write(&self, rocrate: &RoCrate, path: &Path) -> Result<(), FormatError>;
+}
+
+/// The existing `ro-crate-metadata.json` path, via `CustomSerialize`.
+pub struct JsonFormat;
+
+impl CrateFormat for JsonFormat {
+    fn read(&self, path: &Path) -> Result<RoCrate, FormatError> {
+        Ok(read_crate(path, 0)?)
+    }
+
+    fn write(&self, rocrate: &RoCrate, path: &Path) -> Result<(), FormatError> {
+        Ok(write_crate(rocrate, path.to_string_lossy().into_owned())?)
+    }
+}
+
+/// The long-format Parquet frame from `convert`.
+pub struct ParquetFormat;
+
+impl CrateFormat for ParquetFormat {
+    fn read(&self, path: &Path) -> Result<RoCrate, FormatError> {
+        Ok(read_parquet(path)?)
+    }
+
+    fn write(&self, rocrate: &RoCrate, path: &Path) -> Result<(), FormatError> {
+        Ok(write_parquet(rocrate, path)?)
+    }
+}
+
+/// RON (Rusty Object Notation), via the same `Serialize`/`Deserialize` impls as JSON.
+pub struct RonFormat;
+
+impl CrateFormat for RonFormat {
+    fn read(&self, path: &Path) -> Result<RoCrate, FormatError> {
+        Ok(from_ron(path)?)
+    }
+
+    fn write(&self, rocrate: &RoCrate, path: &Path) -> Result<(), FormatError> {
+        Ok(to_ron(rocrate, path.to_string_lossy().into_owned())?)
+    }
+}
+
+/// Maps a file extension to the [`CrateFormat`] that handles it.
+///
+/// Comes pre-populated with `json`, `parquet`, and `ron`; call
+/// [`register`](Self::register) to add more (or to override one of the defaults)
+/// without needing to touch this module.
+pub struct FormatRegistry {
+    formats: HashMap<String, Box<dyn CrateFormat>>,
+}
+
+impl FormatRegistry {
+    pub fn new() -> Self {
+        let mut formats: HashMap<String, Box<dyn CrateFormat>> = HashMap::new();
+        formats.insert("json".to_string(), Box::new(JsonFormat));
+        formats.insert("parquet".to_string(), Box::new(ParquetFormat));
+        formats.insert("ron".to_string(), Box::new(RonFormat));
+        FormatRegistry { formats }
+    }
+
+    /// Registers (or overrides) the format used for `extension`.
+    pub fn register(&mut self, extension: &str, format: Box<dyn CrateFormat>) {
+        self.formats.insert(extension.to_string(), format);
+    }
+
+    fn format_for(&self, path: &Path) -> Result<&dyn CrateFormat, FormatError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        self.formats
+            .get(extension)
+            .map(|format| format.as_ref())
+            .ok_or_else(|| FormatError::UnknownExtension(extension.to_string()))
+    }
+
+    pub fn read(&self, path: &Path) -> Result<RoCrate, FormatError> {
+        self.format_for(path)?.read(path)
+    }
+
+    pub fn write(&self, rocrate: &RoCrate, path: &Path) -> Result<(), FormatError> {
+        self.format_for(path)?.write(rocrate, path)
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_for_dispatches_on_extension() {
+        let registry = FormatRegistry::new();
+        assert!(registry.format_for(Path::new("crate.json")).is_ok());
+        assert!(registry.format_for(Path::new("crate.parquet")).is_ok());
+        assert!(registry.format_for(Path::new("crate.ron")).is_ok());
+        assert!(matches!(
+            registry.format_for(Path::new("crate.xyz")),
+            Err(FormatError::UnknownExtension(ext)) if ext == "xyz"
+        ));
+    }
+}
@@ -0,0 +1,394 @@
+//! A small JSONPath evaluator for querying an [`RoCrate`]'s `@graph` without
+//! hand-walking `hasPart`/`distribution` edges.
+//!
+//! Only the subset of JSONPath actually needed by crate consumers is
+//! implemented: child access (`.name`), recursive descent (`..` / `..name`),
+//! wildcard (`*`), array index/slice (`[n]` / `[a:b]`), and equality filters
+//! (`[?(@.field=='value')]`). The crate is first turned into a
+//! `serde_json::Value` (the same representation it serialises to on disk),
+//! then each path segment narrows a working set of matched values in turn.
+
+use crate::ro_crate::rocrate::RoCrate;
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JsonPathError {
+    #[error("JSONPath must start with `$`")]
+    MissingRoot,
+    #[error("unterminated `[...]` segment in `{0}`")]
+    UnterminatedBracket(String),
+    #[error("unsupported filter expression: `{0}`")]
+    UnsupportedFilter(String),
+    #[error("failed to convert crate to JSON: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Child(String),
+    RecursiveDescent(Option<String>),
+    Wildcard,
+    Index(isize),
+    Slice(Option<isize>, Option<isize>),
+    Filter(FilterExpr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterOp {
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FilterExpr {
+    field_path: Vec<String>,
+    op: FilterOp,
+    value: Value,
+}
+
+/// Evaluates `path` against `rocrate`'s `@graph`, returning every matching
+/// JSON value (owned, since a filter or recursive descent can match nodes at
+/// different nesting depths that don't share a common borrow-friendly shape).
+pub fn query(rocrate: &RoCrate, path: &str) -> Result<Vec<Value>, JsonPathError> {
+    let graph = serde_json::to_value(&rocrate.graph)?;
+    query_value(graph, path)
+}
+
+fn query_value(graph: Value, path: &str) -> Result<Vec<Value>, JsonPathError> {
+    let segments = parse_path(path)?;
+
+    let mut nodes = vec![graph];
+    for segment in &segments {
+        nodes = apply_segment(nodes, segment);
+    }
+    Ok(nodes)
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, JsonPathError> {
+    let rest = path.strip_prefix('$').ok_or(JsonPathError::MissingRoot)?;
+    parse_segments(rest)
+}
+
+fn parse_segments(mut rest: &str) -> Result<Vec<Segment>, JsonPathError> {
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("..") {
+            let name_len = tail
+                .find(|c: char| c == '.' || c == '[')
+                .unwrap_or(tail.len());
+            let (name, remainder) = tail.split_at(name_len);
+            if name.is_empty() {
+                segments.push(Segment::RecursiveDescent(None));
+            } else if name == "*" {
+                segments.push(Segment::RecursiveDescent(None));
+                segments.push(Segment::Wildcard);
+            } else {
+                segments.push(Segment::RecursiveDescent(Some(name.to_string())));
+            }
+            rest = remainder;
+        } else if let Some(tail) = rest.strip_prefix('.') {
+            let name_len = tail
+                .find(|c: char| c == '.' || c == '[')
+                .unwrap_or(tail.len());
+            let (name, remainder) = tail.split_at(name_len);
+            segments.push(if name == "*" {
+                Segment::Wildcard
+            } else {
+                Segment::Child(name.to_string())
+            });
+            rest = remainder;
+        } else if let Some(tail) = rest.strip_prefix('[') {
+            // Match the `]` that closes this bracket, skipping nested ones such as the
+            // `['@id']` inside a filter.
+            let mut depth = 0usize;
+            let end = tail
+                .char_indices()
+                .find(|&(_, c)| match c {
+                    '[' => {
+                        depth += 1;
+                        false
+                    }
+                    ']' if depth == 0 => true,
+                    ']' => {
+                        depth -= 1;
+                        false
+                    }
+                    _ => false,
+                })
+                .map(|(i, _)| i)
+                .ok_or_else(|| JsonPathError::UnterminatedBracket(rest.to_string()))?;
+            let (content, remainder) = tail.split_at(end);
+            segments.push(parse_bracket(content)?);
+            rest = &remainder[1..]; // skip the trailing `]`
+        } else {
+            // Tolerate a bare leading name with no `.`/`..` prefix (`$name`).
+            let name_len = rest
+                .find(|c: char| c == '.' || c == '[')
+                .unwrap_or(rest.len());
+            let (name, remainder) = rest.split_at(name_len);
+            segments.push(Segment::Child(name.to_string()));
+            rest = remainder;
+        }
+    }
+
+    Ok(segments)
+}
+
+fn parse_bracket(content: &str) -> Result<Segment, JsonPathError> {
+    if let Some(filter) = content.strip_prefix("?(").and_then(|s| s.strip_suffix(')')) {
+        return Ok(Segment::Filter(parse_filter(filter)?));
+    }
+    if content == "*" {
+        return Ok(Segment::Wildcard);
+    }
+    if let Some(quoted) = strip_quotes(content) {
+        return Ok(Segment::Child(quoted.to_string()));
+    }
+    if let Some((start, end)) = content.split_once(':') {
+        let start = parse_isize(start);
+        let end = parse_isize(end);
+        return Ok(Segment::Slice(start, end));
+    }
+    content
+        .parse::<isize>()
+        .map(Segment::Index)
+        .map_err(|_| JsonPathError::UnsupportedFilter(content.to_string()))
+}
+
+fn parse_isize(s: &str) -> Option<isize> {
+    let s = s.trim();
+    if s.is_empty() {
+        None
+    } else {
+        s.parse().ok()
+    }
+}
+
+fn strip_quotes(s: &str) -> Option<&str> {
+    for quote in ['\'', '"'] {
+        if s.len() >= 2 && s.starts_with(quote) && s.ends_with(quote) {
+            return Some(&s[1..s.len() - 1]);
+        }
+    }
+    None
+}
+
+fn parse_filter(expr: &str) -> Result<FilterExpr, JsonPathError> {
+    let (op, op_str) = if expr.contains("!=") {
+        (FilterOp::Ne, "!=")
+    } else if expr.contains("==") {
+        (FilterOp::Eq, "==")
+    } else {
+        return Err(JsonPathError::UnsupportedFilter(expr.to_string()));
+    };
+
+    let mut parts = expr.splitn(2, op_str);
+    let field = parts
+        .next()
+        .ok_or_else(|| JsonPathError::UnsupportedFilter(expr.to_string()))?
+        .trim();
+    let value = parts
+        .next()
+        .ok_or_else(|| JsonPathError::UnsupportedFilter(expr.to_string()))?
+        .trim();
+
+    let field = field
+        .strip_prefix('@')
+        .ok_or_else(|| JsonPathError::UnsupportedFilter(expr.to_string()))?;
+    let field_path = parse_field_path(field)?;
+
+    let value = strip_quotes(value)
+        .map(|s| Value::String(s.to_string()))
+        .or_else(|| value.parse::<f64>().ok().map(|n| serde_json::json!(n)))
+        .ok_or_else(|| JsonPathError::UnsupportedFilter(expr.to_string()))?;
+
+    Ok(FilterExpr {
+        field_path,
+        op,
+        value,
+    })
+}
+
+/// Parses the dotted/bracketed field path inside a filter's `@...` reference
+/// (e.g. `.conformsTo['@id']`) into plain key names.
+fn parse_field_path(mut rest: &str) -> Result<Vec<String>, JsonPathError> {
+    let mut path = Vec::new();
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('.') {
+            let name_len = tail.find(|c: char| c == '.' || c == '[').unwrap_or(tail.len());
+            let (name, remainder) = tail.split_at(name_len);
+            path.push(name.to_string());
+            rest = remainder;
+        } else if let Some(tail) = rest.strip_prefix('[') {
+            let end = tail
+                .find(']')
+                .ok_or_else(|| JsonPathError::UnterminatedBracket(rest.to_string()))?;
+            let (content, remainder) = tail.split_at(end);
+            let name = strip_quotes(content)
+                .ok_or_else(|| JsonPathError::UnsupportedFilter(content.to_string()))?;
+            path.push(name.to_string());
+            rest = &remainder[1..];
+        } else {
+            return Err(JsonPathError::UnsupportedFilter(rest.to_string()));
+        }
+    }
+    Ok(path)
+}
+
+fn apply_segment(nodes: Vec<Value>, segment: &Segment) -> Vec<Value> {
+    match segment {
+        Segment::Child(name) => nodes
+            .into_iter()
+            .filter_map(|node| node.get(name).cloned())
+            .collect(),
+        Segment::Wildcard => nodes
+            .into_iter()
+            .flat_map(|node| match node {
+                Value::Object(map) => map.into_values().collect::<Vec<_>>(),
+                Value::Array(items) => items,
+                _ => Vec::new(),
+            })
+            .collect(),
+        Segment::Index(index) => nodes
+            .into_iter()
+            .filter_map(|node| match node {
+                Value::Array(items) => resolve_index(&items, *index).cloned(),
+                _ => None,
+            })
+            .collect(),
+        Segment::Slice(start, end) => nodes
+            .into_iter()
+            .flat_map(|node| match node {
+                Value::Array(items) => resolve_slice(items, *start, *end),
+                _ => Vec::new(),
+            })
+            .collect(),
+        Segment::RecursiveDescent(name) => {
+            let mut collected = Vec::new();
+            for node in &nodes {
+                collect_descendants(node, &mut collected);
+            }
+            match name {
+                None => collected,
+                Some(name) => collected
+                    .into_iter()
+                    .filter_map(|node| node.get(name).cloned())
+                    .collect(),
+            }
+        }
+        Segment::Filter(expr) => nodes
+            .into_iter()
+            .filter(|node| matches_filter(node, expr))
+            .collect(),
+    }
+}
+
+fn collect_descendants(node: &Value, out: &mut Vec<Value>) {
+    out.push(node.clone());
+    match node {
+        Value::Object(map) => {
+            for value in map.values() {
+                collect_descendants(value, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_descendants(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_index(items: &[Value], index: isize) -> Option<&Value> {
+    let len = items.len() as isize;
+    let normalised = if index < 0 { len + index } else { index };
+    if normalised < 0 {
+        None
+    } else {
+        items.get(normalised as usize)
+    }
+}
+
+fn resolve_slice(items: Vec<Value>, start: Option<isize>, end: Option<isize>) -> Vec<Value> {
+    let len = items.len() as isize;
+    let normalise = |value: isize| -> usize {
+        let value = if value < 0 { len + value } else { value };
+        value.clamp(0, len) as usize
+    };
+    let start = start.map(normalise).unwrap_or(0);
+    let end = end.map(normalise).unwrap_or(items.len());
+    if start >= end {
+        Vec::new()
+    } else {
+        items[start..end].to_vec()
+    }
+}
+
+fn matches_filter(node: &Value, expr: &FilterExpr) -> bool {
+    let mut current = node;
+    for key in &expr.field_path {
+        match current.get(key) {
+            Some(next) => current = next,
+            None => return false,
+        }
+    }
+    match expr.op {
+        FilterOp::Eq => current == &expr.value,
+        FilterOp::Ne => current != &expr.value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_and_wildcard() {
+        let graph = serde_json::json!([
+            {"@id": "./", "@type": "Dataset", "name": "root"},
+            {"@id": "a.txt", "@type": "File"},
+        ]);
+
+        let ids = query_value(graph, "$.*['@id']").unwrap();
+        assert_eq!(
+            ids,
+            vec![
+                Value::String("./".to_string()),
+                Value::String("a.txt".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_descent_filter() {
+        let graph = serde_json::json!([
+            {"@id": "./", "@type": "Dataset", "conformsTo": {"@id": "https://w3id.org/ro/crate"}},
+            {"@id": "sub/", "@type": "Dataset", "conformsTo": {"@id": "https://example.org/other"}},
+        ]);
+
+        let matches = query_value(
+            graph,
+            "$..[?(@.conformsTo['@id']=='https://w3id.org/ro/crate')]",
+        )
+        .unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["@id"], Value::String("./".to_string()));
+    }
+
+    #[test]
+    fn test_index_and_slice() {
+        let graph = serde_json::json!([
+            {"@id": "a"},
+            {"@id": "b"},
+            {"@id": "c"},
+        ]);
+
+        assert_eq!(query_value(graph.clone(), "$[0]").unwrap()[0]["@id"], "a");
+        assert_eq!(query_value(graph.clone(), "$[-1]").unwrap()[0]["@id"], "c");
+        assert_eq!(query_value(graph, "$[1:3]").unwrap().len(), 2);
+    }
+}
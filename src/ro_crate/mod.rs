@@ -4,9 +4,14 @@
 //! Serialisatoin and deserialisation of RO-Crates to json-ld files heavily leverages
 //! the serde and serde-json library
 
+pub mod binary;
+pub mod bio_profile;
+pub mod canonical;
 pub mod constraints;
 pub mod contextual_entity;
 pub mod data_entity;
+pub mod format;
+pub mod jsonpath;
 pub mod metadata_descriptor;
 pub mod modify;
 pub mod read;
@@ -6,11 +6,12 @@
 //! # Note
 //! This should definitly be split up in future implementations
 
-use crate::ro_crate::constraints::EntityValue;
+use crate::ro_crate::constraints::{DataType, EntityValue, Id};
+use crate::ro_crate::data_entity::DataEntity;
 use crate::ro_crate::graph_vector::GraphVector;
 use crate::ro_crate::modify::DynamicEntityManipulation;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// Represents a Research Object Crate (RO-Crate) metadata structure.
@@ -36,6 +37,17 @@ pub struct RoCrate {
     /// format, allowing for easy machine processing and interoperability.
     #[serde(rename = "@graph")]
     pub graph: Vec<GraphVector>,
+
+    /// Lazily-built `@id -> graph index` lookup table.
+    ///
+    /// Every id-based lookup used to linearly scan `graph`, which made recursive
+    /// ID rewrites O(n^2). This mirrors the lazy indexing rustc's metadata decoder
+    /// uses for its `def_path_hash_map`: the table is populated on first use and
+    /// then kept in sync by the mutating methods below. `ensure_index` also rebuilds
+    /// it whenever its length no longer matches `graph`; an `@id` changed in place
+    /// through the public `graph` field is not detected.
+    #[serde(skip)]
+    id_index: HashMap<String, usize>,
 }
 
 /// Defines the JSON-LD contexts in an RO-Crate, facilitating flexible context specification.
@@ -72,6 +84,69 @@ pub enum ContextItem {
     EmbeddedContext(HashMap<String, String>),
 }
 
+/// A cache consulted before fetching a remote JSON-LD context document for term resolution,
+/// and populated afterwards, so resolving several terms against the same
+/// [`RoCrateContext::ReferenceContext`] only issues one GET. Mirrors
+/// [`crate::ro_crate::subcrate_resolution::SubcrateCache`]'s get/put shape.
+pub trait ContextCache {
+    fn get(&mut self, url: &str) -> Option<HashMap<String, String>>;
+    fn put(&mut self, url: &str, context: &HashMap<String, String>);
+}
+
+/// Fetches and parses a remote JSON-LD context document into a flat `term -> IRI` map.
+///
+/// Handles both styles of term definition a context document can use: a plain IRI string, or
+/// an expanded term definition object with an `@id` key.
+fn fetch_remote_context(url: &str) -> Result<HashMap<String, String>, String> {
+    let response = reqwest::blocking::get(url).map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let document: serde_json::Value = response.json().map_err(|e| e.to_string())?;
+    let terms = document
+        .get("@context")
+        .and_then(|context| context.as_object())
+        .ok_or_else(|| "context document has no `@context` object".to_string())?;
+
+    Ok(terms
+        .iter()
+        .filter_map(|(term, value)| {
+            let iri = match value {
+                serde_json::Value::String(iri) => Some(iri.clone()),
+                serde_json::Value::Object(definition) => definition
+                    .get("@id")
+                    .and_then(|id| id.as_str())
+                    .map(str::to_string),
+                _ => None,
+            };
+            iri.map(|iri| (term.clone(), iri))
+        })
+        .collect())
+}
+
+/// A cheap, per-entity audit entry produced by [`RoCrate::inspect`].
+///
+/// Reports just enough to decide whether an entity is worth loading in full: its
+/// `@id`, `@type`, and every other `@id` it references.
+#[derive(Debug, Serialize)]
+pub struct EntitySummary {
+    pub id: String,
+    pub type_: String,
+    pub references: Vec<String>,
+}
+
+/// Result of [`RoCrate::inspect`]: what the crate contains, as a compact
+/// per-entity listing plus aggregate counts.
+///
+/// Useful for auditing large crates or finding broken links before editing.
+#[derive(Debug, Serialize)]
+pub struct CrateSummary {
+    pub entities: Vec<EntitySummary>,
+    pub data_entity_count: usize,
+    pub contextual_entity_count: usize,
+    pub dangling_references: Vec<String>,
+}
+
 /// This allows direct manipulation of each node of the GraphVector
 impl RoCrate {
     /// Creates a new struct with a given context and empty Graph vec (i.e no entities)
@@ -79,6 +154,7 @@ impl RoCrate {
         RoCrate {
             context,
             graph: Vec::new(),
+            id_index: HashMap::new(),
         }
     }
 
@@ -117,8 +193,140 @@ impl RoCrate {
         valid_context
     }
 
-    /// TODO
-    pub fn add_context(&self) {}
+    /// Adds a term definition to the crate's `@context`.
+    ///
+    /// If the context is currently embedded (or extended), the definition is inserted into
+    /// the first embedded map, creating one if none exists yet. If the context is currently
+    /// just a [`RoCrateContext::ReferenceContext`] URL, it is promoted to an
+    /// [`RoCrateContext::ExtendedContext`] that keeps the reference alongside a new embedded
+    /// map holding `term`.
+    pub fn add_context(&mut self, term: &str, iri: &str) {
+        match &mut self.context {
+            RoCrateContext::EmbeddedContext(maps) => {
+                if maps.is_empty() {
+                    maps.push(HashMap::new());
+                }
+                maps[0].insert(term.to_string(), iri.to_string());
+            }
+            RoCrateContext::ExtendedContext(items) => {
+                if let Some(ContextItem::EmbeddedContext(map)) = items
+                    .iter_mut()
+                    .find(|item| matches!(item, ContextItem::EmbeddedContext(_)))
+                {
+                    map.insert(term.to_string(), iri.to_string());
+                } else {
+                    items.push(ContextItem::EmbeddedContext(HashMap::from([(
+                        term.to_string(),
+                        iri.to_string(),
+                    )])));
+                }
+            }
+            RoCrateContext::ReferenceContext(reference) => {
+                self.context = RoCrateContext::ExtendedContext(vec![
+                    ContextItem::ReferenceItem(reference.clone()),
+                    ContextItem::EmbeddedContext(HashMap::from([(
+                        term.to_string(),
+                        iri.to_string(),
+                    )])),
+                ]);
+            }
+        }
+    }
+
+    /// Removes a term definition from the crate's embedded `@context`, if present.
+    ///
+    /// Has no effect on a [`RoCrateContext::ReferenceContext`]: there is no local definition
+    /// to remove, just a document to stop resolving against.
+    pub fn remove_context(&mut self, term: &str) {
+        match &mut self.context {
+            RoCrateContext::EmbeddedContext(maps) => {
+                for map in maps {
+                    map.remove(term);
+                }
+            }
+            RoCrateContext::ExtendedContext(items) => {
+                for item in items {
+                    if let ContextItem::EmbeddedContext(map) = item {
+                        map.remove(term);
+                    }
+                }
+            }
+            RoCrateContext::ReferenceContext(_) => {}
+        }
+    }
+
+    /// Returns the IRI `term` expands to, per the crate's `@context`.
+    ///
+    /// Embedded definitions are searched first and never touch the network. If the crate
+    /// declares a [`RoCrateContext::ReferenceContext`] URL (or one inside an
+    /// [`RoCrateContext::ExtendedContext`]), the context document is fetched and searched
+    /// too. See [`Self::resolve_term_cached`] to avoid refetching it on every call.
+    pub fn resolve_term(&self, term: &str) -> Option<String> {
+        self.resolve_term_cached(term, &mut None)
+    }
+
+    /// Same resolution as [`Self::resolve_term`], but consulting `cache` before fetching a
+    /// remote context document and populating it afterwards - the cache-aware counterpart for
+    /// callers resolving many terms against the same remote context, mirroring
+    /// [`crate::ro_crate::subcrate_resolution::SubcrateCache`].
+    pub fn resolve_term_cached(
+        &self,
+        term: &str,
+        cache: &mut Option<&mut dyn ContextCache>,
+    ) -> Option<String> {
+        if let Some(iri) = self.resolve_term_embedded(term) {
+            return Some(iri);
+        }
+
+        for url in self.reference_context_urls() {
+            let terms = match cache.as_deref_mut().and_then(|cache| cache.get(&url)) {
+                Some(cached) => cached,
+                None => {
+                    let Ok(fetched) = fetch_remote_context(&url) else {
+                        continue;
+                    };
+                    if let Some(cache) = cache.as_deref_mut() {
+                        cache.put(&url, &fetched);
+                    }
+                    fetched
+                }
+            };
+            if let Some(iri) = terms.get(term) {
+                return Some(iri.clone());
+            }
+        }
+
+        None
+    }
+
+    /// Searches only the crate's embedded term definitions, never the network.
+    fn resolve_term_embedded(&self, term: &str) -> Option<String> {
+        match &self.context {
+            RoCrateContext::EmbeddedContext(maps) => {
+                maps.iter().find_map(|map| map.get(term).cloned())
+            }
+            RoCrateContext::ExtendedContext(items) => items.iter().find_map(|item| match item {
+                ContextItem::EmbeddedContext(map) => map.get(term).cloned(),
+                ContextItem::ReferenceItem(_) => None,
+            }),
+            RoCrateContext::ReferenceContext(_) => None,
+        }
+    }
+
+    /// Collects every external context document URL the crate's `@context` references.
+    fn reference_context_urls(&self) -> Vec<String> {
+        match &self.context {
+            RoCrateContext::ReferenceContext(url) => vec![url.clone()],
+            RoCrateContext::ExtendedContext(items) => items
+                .iter()
+                .filter_map(|item| match item {
+                    ContextItem::ReferenceItem(url) => Some(url.clone()),
+                    ContextItem::EmbeddedContext(_) => None,
+                })
+                .collect(),
+            RoCrateContext::EmbeddedContext(_) => Vec::new(),
+        }
+    }
 
     /// Returns entity based on ID
     pub fn get_entity(&self, id: &str) -> Option<&GraphVector> {
@@ -140,6 +348,60 @@ impl RoCrate {
         None
     }
 
+    /// Audits the graph of this already-loaded crate; entities are not read lazily.
+    ///
+    /// For each entity, reports its `@id`, `@type`, and the `@id`s it references (found
+    /// via the same per-property `EntityValue::EntityId` scan that backs
+    /// `get_all_property_values`), plus aggregate entity counts and any referenced `@id`s
+    /// that aren't defined anywhere in the graph.
+    pub fn inspect(&self) -> CrateSummary {
+        let known_ids: HashSet<&String> = self.graph.iter().map(|gv| gv.get_id()).collect();
+        let mut referenced: HashSet<String> = HashSet::new();
+        let mut data_entity_count = 0;
+        let mut contextual_entity_count = 0;
+
+        let entities = self
+            .graph
+            .iter()
+            .map(|graph_vector| {
+                match graph_vector {
+                    GraphVector::DataEntity(_) => data_entity_count += 1,
+                    GraphVector::ContextualEntity(_) => contextual_entity_count += 1,
+                    _ => {}
+                }
+
+                let type_ = entity_type_label(graph_vector);
+
+                let references: Vec<String> = graph_vector
+                    .get_all_properties()
+                    .into_iter()
+                    .filter_map(|property| graph_vector.get_specific_property(&property))
+                    .flat_map(|(_, value)| entity_value_ids(&value))
+                    .collect();
+                referenced.extend(references.iter().cloned());
+
+                EntitySummary {
+                    id: graph_vector.get_id().clone(),
+                    type_,
+                    references,
+                }
+            })
+            .collect();
+
+        let mut dangling_references: Vec<String> = referenced
+            .into_iter()
+            .filter(|id| !known_ids.contains(id))
+            .collect();
+        dangling_references.sort();
+
+        CrateSummary {
+            entities,
+            data_entity_count,
+            contextual_entity_count,
+            dangling_references,
+        }
+    }
+
     /// Retrieves a list of all entity IDs within the RO-Crate.
     ///
     /// This method compiles a list of the IDs of all entities contained within the RO-Crate. It is useful
@@ -154,21 +416,37 @@ impl RoCrate {
         id_vec
     }
 
+    /// Rebuilds the `@id -> index` lookup table from the current graph.
+    ///
+    /// Called whenever the table can no longer be trusted to match `graph`
+    /// (e.g. after a removal, which shifts every following index).
+    fn rebuild_index(&mut self) {
+        self.id_index = self
+            .graph
+            .iter()
+            .enumerate()
+            .map(|(index, graph_vector)| (graph_vector.get_id().clone(), index))
+            .collect();
+    }
+
+    /// Ensures the lookup table is present and consistent with `graph`.
+    ///
+    /// The table starts empty (it is `#[serde(skip)]`), so this also doubles
+    /// as the "build once on first use" step for freshly deserialized crates.
+    fn ensure_index(&mut self) {
+        if self.id_index.len() != self.graph.len() {
+            self.rebuild_index();
+        }
+    }
+
     /// Finds the index of a particular entity in the RO-Crate graph based on its `@id`.
     ///
     /// Returns the index of the first entity that matches the given `@id`.
-    /// Returns `None` if no match is found.
+    /// Returns `None` if no match is found. Backed by the `id_index` table, so
+    /// this is O(1) after the first call instead of a linear scan.
     pub fn find_entity_index(&mut self, id: &str) -> Option<usize> {
-        self.graph
-            .iter()
-            .enumerate()
-            .find_map(|(index, graph_vector)| {
-                if graph_vector.get_id() == id {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
+        self.ensure_index();
+        self.id_index.get(id).copied()
     }
 
     /// Finds ID based upon ID string input and returns a reference to it.
@@ -192,6 +470,8 @@ impl RoCrate {
                 GraphVector::DataEntity(entity) => entity.id != id_to_remove,
                 GraphVector::ContextualEntity(entity) => entity.id != id_to_remove,
             });
+        // Removal shifts every following index, so a full rebuild is simplest.
+        self.rebuild_index();
 
         if rec {
             self.remove_id_recursive(id_to_remove)
@@ -224,6 +504,7 @@ impl RoCrate {
     /// Looks through all entities, updating any that match `id_old` to `id_new`. If any entity is updated,
     /// it returns a confirmation. This is useful for keeping the crate's links accurate if an entity's ID changes.
     pub fn update_id_recursive(&mut self, id_old: &str, id_new: &str) {
+        self.ensure_index();
         for graph_vector in &mut self.graph {
             if graph_vector.get_id() == id_old {
                 graph_vector.update_id(id_new.to_string());
@@ -232,6 +513,11 @@ impl RoCrate {
                 graph_vector.update_id_link(id_old, id_new);
             };
         }
+        // Rekey rather than rebuild: the renamed entity keeps its slot, every
+        // other entity's index is untouched.
+        if let Some(index) = self.id_index.remove(id_old) {
+            self.id_index.insert(id_new.to_string(), index);
+        }
     }
 
     /// Ensures a data entity is included in the `hasPart` property of the root data entity.
@@ -245,6 +531,70 @@ impl RoCrate {
         };
     }
 
+    /// Creates a directory data entity: a `Dataset`-typed [`DataEntity`] whose `@id` ends in
+    /// `/`, the RO-Crate convention that distinguishes directories from files (the reference
+    /// implementations keep these as distinct types for the same reason). `id` is normalized
+    /// to end in `/` if it doesn't already. If an entity with that id already exists, this
+    /// leaves it untouched. Either way the (possibly pre-existing) entity is linked into the
+    /// root data entity's `hasPart`, if the crate has a root data entity.
+    pub fn add_directory_entity(&mut self, id: &str) -> String {
+        let id = normalize_directory_id(id);
+
+        if self.find_entity_index(&id).is_none() {
+            self.graph.push(GraphVector::DataEntity(DataEntity {
+                id: id.clone(),
+                type_: DataType::Term("Dataset".to_string()),
+                dynamic_entity: None,
+            }));
+            self.rebuild_index();
+        }
+
+        if let Some(GraphVector::RootDataEntity(root)) = self
+            .graph
+            .iter_mut()
+            .find(|graph_vector| matches!(graph_vector, GraphVector::RootDataEntity(_)))
+        {
+            root.add_entity_to_partof(id.clone());
+        }
+        id
+    }
+
+    /// Returns every entity logically contained under `directory_id`, i.e. whose `@id` is a
+    /// strict path-prefix match against it - the same path-tree containment a filesystem
+    /// directory listing has, including entities nested under subdirectories. `directory_id`
+    /// is normalized to end in `/` first (as [`Self::add_directory_entity`] does when creating
+    /// one), so `"data"` and `"data/"` match the same entities and a sibling like
+    /// `"database/file.txt"` is never mistaken for being contained under `"data/"`.
+    pub fn entities_under_directory(&self, directory_id: &str) -> Vec<&GraphVector> {
+        let directory_id = normalize_directory_id(directory_id);
+        self.graph
+            .iter()
+            .filter(|graph_vector| {
+                let id = graph_vector.get_id();
+                id.as_str() != directory_id && id.starts_with(&directory_id)
+            })
+            .collect()
+    }
+
+    /// Flags entities that are logically contained under `directory_id` (by path prefix) but
+    /// aren't listed in the directory entity's `hasPart`. Surfaces the gap the gem's
+    /// `directory.rb`/`data_entity.rb` split is meant to prevent: a crate whose declared
+    /// `hasPart` has drifted from the files actually packaged under that path.
+    pub fn missing_has_part_entries(&self, directory_id: &str) -> Vec<String> {
+        let directory_id = normalize_directory_id(directory_id);
+        let has_part: HashSet<String> = self
+            .get_entity(&directory_id)
+            .and_then(|graph_vector| graph_vector.get_specific_property("hasPart"))
+            .map(|(_, value)| entity_value_ids(&value).into_iter().collect())
+            .unwrap_or_default();
+
+        self.entities_under_directory(&directory_id)
+            .into_iter()
+            .map(|graph_vector| graph_vector.get_id().clone())
+            .filter(|id| !has_part.contains(id))
+            .collect()
+    }
+
     pub fn get_all_properties(&self) -> Vec<String> {
         let mut properties: Vec<String> = Vec::new();
         for graph_vector in &self.graph {
@@ -282,7 +632,13 @@ impl RoCrate {
         println!("id: {}", id);
         println!("Entity: {:?}", entity);
         if let Some(index) = self.find_entity_index(id) {
+            let new_id = entity.get_id().clone();
             self.graph[index] = entity;
+            // Same slot, but the `@id` may have changed as part of the overwrite.
+            if new_id != id {
+                self.id_index.remove(id);
+                self.id_index.insert(new_id, index);
+            }
             true
         } else {
             false
@@ -310,6 +666,213 @@ impl RoCrate {
             false
         }
     }
+
+    /// Serializes this crate to its canonical JSON-LD form: object keys sorted
+    /// lexicographically, compact separators, stable number/string formatting. Two
+    /// independently built but structurally equal crates always produce identical
+    /// bytes, which is what makes this form suitable for signing or content-addressing.
+    pub fn to_canonical_string(&self) -> Result<String, serde_json::Error> {
+        crate::ro_crate::canonical::to_canonical_string(self)
+    }
+
+    /// Queries the `@graph` with a JSONPath expression, e.g.
+    /// `$..[?(@.conformsTo['@id']=='https://w3id.org/ro/crate')]` to find every
+    /// nested subcrate descriptor. See [`crate::ro_crate::jsonpath`] for the
+    /// supported syntax.
+    pub fn query_jsonpath(
+        &self,
+        path: &str,
+    ) -> Result<Vec<serde_json::Value>, crate::ro_crate::jsonpath::JsonPathError> {
+        crate::ro_crate::jsonpath::query(self, path)
+    }
+
+    /// Renders the `@graph` as a Graphviz DOT digraph: one node per entity, labelled
+    /// with its `@id`, `@type`, and `name` (if set), and one edge per property whose
+    /// value is an `@id` reference (or list of references), labelled with the property
+    /// name. Mirrors rust-analyzer's `view_crate_graph` debug command, which serialises
+    /// its own dependency graph the same way for visual inspection.
+    ///
+    /// A reference to an `@id` that isn't defined anywhere in the graph still gets an
+    /// edge, but the target node is rendered dashed and in red so broken links are
+    /// obvious at a glance rather than silently missing from the picture.
+    ///
+    /// Pipe the result into `dot -Tsvg` (or any other Graphviz renderer) to view it.
+    pub fn to_dot(&self) -> String {
+        let known_ids: HashSet<&String> = self.graph.iter().map(|gv| gv.get_id()).collect();
+        let mut dangling: HashSet<String> = HashSet::new();
+        let mut dot = String::from("digraph ROCrate {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n\n");
+
+        for graph_vector in &self.graph {
+            let id = graph_vector.get_id();
+            dot.push_str(&format!(
+                "    {:?} [label={:?}];\n",
+                id,
+                node_label(graph_vector)
+            ));
+        }
+        dot.push('\n');
+
+        for graph_vector in &self.graph {
+            let source = graph_vector.get_id();
+            for property in graph_vector.get_all_properties() {
+                let Some((_, value)) = graph_vector.get_specific_property(&property) else {
+                    continue;
+                };
+
+                for target in entity_value_ids(&value) {
+                    if !known_ids.contains(&target) {
+                        dangling.insert(target.clone());
+                    }
+                    dot.push_str(&format!(
+                        "    {:?} -> {:?} [label={:?}];\n",
+                        source, target, property
+                    ));
+                }
+            }
+        }
+
+        if !dangling.is_empty() {
+            let mut dangling: Vec<String> = dangling.into_iter().collect();
+            dangling.sort();
+            dot.push('\n');
+            for id in dangling {
+                dot.push_str(&format!(
+                    "    {:?} [label={:?}, style=\"rounded,dashed\", color=red, fontcolor=red];\n",
+                    id, id
+                ));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders a standalone `ro-crate-preview.html` page: the root data entity's
+    /// name/description/license/authors, followed by a browsable list of every other
+    /// entity with its properties, `@id` references resolved to in-page anchors where
+    /// the target is actually present in the graph. Matches the shape the reference
+    /// Ruby `ro_crate` gem's `preview_generator` produces, built from the same
+    /// `get_specific_property` accessor other read-only
+    /// tooling in this crate already uses rather than a bespoke HTML-specific walk.
+    pub fn to_preview_html(&self) -> String {
+        let root = self
+            .graph
+            .iter()
+            .find(|gv| matches!(gv, GraphVector::RootDataEntity(_)));
+
+        let title = root
+            .and_then(|gv| gv.get_specific_property("name"))
+            .and_then(|(_, value)| match value {
+                EntityValue::EntityString(name) => Some(name),
+                _ => None,
+            })
+            .unwrap_or_else(|| "RO-Crate".to_string());
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!(
+            "<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n",
+            title = escape_html(&title)
+        ));
+
+        if let Some(root) = root {
+            if let Some((_, EntityValue::EntityString(description))) =
+                root.get_specific_property("description")
+            {
+                html.push_str(&format!("<p>{}</p>\n", escape_html(&description)));
+            }
+
+            html.push_str("<dl>\n");
+            if let Some((_, EntityValue::EntityString(date_published))) =
+                root.get_specific_property("datePublished")
+            {
+                html.push_str(&format!(
+                    "<dt>Published</dt><dd>{}</dd>\n",
+                    escape_html(&date_published)
+                ));
+            }
+            if let Some((_, value)) = root.get_specific_property("license") {
+                for license_id in entity_value_ids(&value) {
+                    html.push_str(&format!(
+                        "<dt>License</dt><dd>{}</dd>\n",
+                        entity_link(self, &license_id)
+                    ));
+                }
+                if let EntityValue::EntityString(description) = &value {
+                    html.push_str(&format!(
+                        "<dt>License</dt><dd>{}</dd>\n",
+                        escape_html(description)
+                    ));
+                }
+            }
+            if let Some((_, value)) = root.get_specific_property("author") {
+                for author_id in entity_value_ids(&value) {
+                    html.push_str(&format!(
+                        "<dt>Author</dt><dd>{}</dd>\n",
+                        entity_link(self, &author_id)
+                    ));
+                }
+            }
+            html.push_str("</dl>\n");
+        }
+
+        html.push_str("<h2>Entities</h2>\n<ul>\n");
+        for graph_vector in &self.graph {
+            if matches!(graph_vector, GraphVector::MetadataDescriptor(_)) {
+                continue;
+            }
+            html.push_str(&format!(
+                "<li><a href=\"#{anchor}\">{id}</a> ({type_})</li>\n",
+                anchor = anchor_id(graph_vector.get_id()),
+                id = escape_html(graph_vector.get_id()),
+                type_ = escape_html(&entity_type_label(graph_vector))
+            ));
+        }
+        html.push_str("</ul>\n");
+
+        for graph_vector in &self.graph {
+            if matches!(graph_vector, GraphVector::MetadataDescriptor(_)) {
+                continue;
+            }
+            html.push_str(&format!(
+                "<section id=\"{anchor}\">\n<h3>{id}</h3>\n<p>{type_}</p>\n<dl>\n",
+                anchor = anchor_id(graph_vector.get_id()),
+                id = escape_html(graph_vector.get_id()),
+                type_ = escape_html(&entity_type_label(graph_vector))
+            ));
+
+            for property in graph_vector.get_all_properties() {
+                let Some((_, value)) = graph_vector.get_specific_property(&property) else {
+                    continue;
+                };
+
+                let rendered = match &value {
+                    EntityValue::EntityString(s) => escape_html(s),
+                    EntityValue::Entityi64(n) => n.to_string(),
+                    EntityValue::Entityf64(n) => n.to_string(),
+                    EntityValue::EntityBool(Some(b)) => b.to_string(),
+                    EntityValue::EntityBool(None) => "null".to_string(),
+                    EntityValue::EntityId(_) => entity_value_ids(&value)
+                        .iter()
+                        .map(|id| entity_link(self, id))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    other => escape_html(&format!("{other:?}")),
+                };
+
+                html.push_str(&format!(
+                    "<dt>{}</dt><dd>{}</dd>\n",
+                    escape_html(&property),
+                    rendered
+                ));
+            }
+
+            html.push_str("</dl>\n</section>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
 }
 
 impl Default for RoCrate {
@@ -323,6 +886,7 @@ impl Default for RoCrate {
                 "https://w3id.org/ro/crate/1.1/context",
             )),
             graph: Vec::new(),
+            id_index: HashMap::new(),
         }
     }
 }
@@ -366,6 +930,100 @@ fn dedup_vec<T: Ord>(vec: &mut Vec<T>) {
     vec.sort();
     vec.dedup();
 }
+
+/// The `@type` of a `GraphVector`, regardless of which of the four variants it is.
+fn entity_type_label(graph_vector: &GraphVector) -> String {
+    match graph_vector {
+        GraphVector::MetadataDescriptor(e) => format!("{:?}", e.type_),
+        GraphVector::RootDataEntity(e) => format!("{:?}", e.type_),
+        GraphVector::DataEntity(e) => format!("{:?}", e.type_),
+        GraphVector::ContextualEntity(e) => format!("{:?}", e.type_),
+    }
+}
+
+/// A `to_dot` node label: the `@id`, its `@type`, and its `name` property if the
+/// entity has one - the combination reference RO-Crate viewers (and rust-analyzer's
+/// crate graph nodes) use to make a rendered graph readable without clicking into
+/// every node.
+fn node_label(graph_vector: &GraphVector) -> String {
+    let name = match graph_vector.get_specific_property("name") {
+        Some((_, EntityValue::EntityString(name))) => Some(name),
+        _ => None,
+    };
+
+    match name {
+        Some(name) => format!(
+            "{}\n{}\n({})",
+            graph_vector.get_id(),
+            name,
+            entity_type_label(graph_vector)
+        ),
+        None => format!(
+            "{}\n({})",
+            graph_vector.get_id(),
+            entity_type_label(graph_vector)
+        ),
+    }
+}
+
+/// Normalizes a directory `@id` to end in `/`, the RO-Crate convention
+/// [`RoCrate::add_directory_entity`] applies when creating one. Shared with
+/// [`RoCrate::entities_under_directory`]/[`RoCrate::missing_has_part_entries`] so a caller
+/// passing either `"data"` or `"data/"` gets the same, correctly-scoped answer.
+fn normalize_directory_id(id: &str) -> String {
+    if id.ends_with('/') {
+        id.to_string()
+    } else {
+        format!("{id}/")
+    }
+}
+
+/// Extracts every `@id` an `EntityValue` resolves to, whether it's a single reference
+/// or an array of references.
+fn entity_value_ids(value: &EntityValue) -> Vec<String> {
+    match value {
+        EntityValue::EntityId(Id::Id(id_value)) => vec![id_value.id.clone()],
+        EntityValue::EntityId(Id::IdArray(id_values)) => {
+            id_values.iter().map(|id_value| id_value.id.clone()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Escapes the characters that would otherwise break out of HTML text/attribute
+/// context. Not a full sanitizer - there's no untrusted markup being embedded, just
+/// entity metadata that may happen to contain `<`, `>`, `&`, or `"`.
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Turns an `@id` into a valid HTML `id` attribute value by replacing every character
+/// outside `[A-Za-z0-9]` with `-`. IDs in an RO-Crate are frequently full URIs or
+/// relative paths, neither of which are valid HTML anchor names as-is.
+fn anchor_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Renders a cross-reference as a clickable in-page anchor if `id` names an entity
+/// actually present in the graph, or as plain escaped text otherwise (e.g. an external
+/// URI, or a dangling reference).
+fn entity_link(rocrate: &RoCrate, id: &str) -> String {
+    if rocrate.graph.iter().any(|gv| gv.get_id() == id) {
+        format!(
+            "<a href=\"#{}\">{}</a>",
+            anchor_id(id),
+            escape_html(id)
+        )
+    } else {
+        escape_html(id)
+    }
+}
 // Tests to make
 
 // Parses valid into dataEntity's if a file
@@ -393,3 +1051,126 @@ fn dedup_vec<T: Ord>(vec: &mut Vec<T>) {
 
 // Check that try_deserilaise into graph vector gets correct ID
 // Check that corect match arms and called when id matches valid crate objects
+
+#[cfg(test)]
+mod directory_entity_tests {
+    use super::*;
+    use crate::ro_crate::constraints::IdValue;
+    use crate::ro_crate::modify::DynamicEntity;
+
+    fn file_entity(id: &str) -> GraphVector {
+        GraphVector::DataEntity(DataEntity {
+            id: id.to_string(),
+            type_: DataType::Term("File".to_string()),
+            dynamic_entity: None,
+        })
+    }
+
+    fn directory_entity_with_has_part(id: &str, has_part: Vec<&str>) -> GraphVector {
+        GraphVector::DataEntity(DataEntity {
+            id: id.to_string(),
+            type_: DataType::Term("Dataset".to_string()),
+            dynamic_entity: Some(HashMap::from([(
+                "hasPart".to_string(),
+                DynamicEntity::EntityId(Id::IdArray(
+                    has_part
+                        .into_iter()
+                        .map(|id| IdValue { id: id.to_string() })
+                        .collect(),
+                )),
+            )])),
+        })
+    }
+
+    fn empty_crate() -> RoCrate {
+        RoCrate::new(
+            RoCrateContext::ReferenceContext("https://w3id.org/ro/crate/1.1/context".to_string()),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn add_directory_entity_normalizes_id_to_end_in_slash() {
+        let mut rocrate = empty_crate();
+
+        let id = rocrate.add_directory_entity("data");
+
+        assert_eq!(id, "data/");
+        match rocrate.find_entity("data/") {
+            Some(GraphVector::DataEntity(entity)) => {
+                assert!(matches!(&entity.type_, DataType::Term(term) if term == "Dataset"));
+            }
+            other => panic!("expected a Dataset DataEntity at \"data/\", got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn add_directory_entity_is_idempotent() {
+        let mut rocrate = empty_crate();
+
+        rocrate.add_directory_entity("data/");
+        rocrate.add_directory_entity("data/");
+
+        let matching = rocrate
+            .graph
+            .iter()
+            .filter(|graph_vector| graph_vector.get_id() == "data/")
+            .count();
+        assert_eq!(matching, 1);
+    }
+
+    #[test]
+    fn entities_under_directory_normalizes_trailing_slash_and_excludes_siblings() {
+        let mut rocrate = empty_crate();
+        rocrate.graph.push(file_entity("data/"));
+        rocrate.graph.push(file_entity("data/a.txt"));
+        rocrate.graph.push(file_entity("data/sub/b.txt"));
+        rocrate.graph.push(file_entity("database/other.txt"));
+        rocrate.graph.push(file_entity("data2/x"));
+
+        let expected: HashSet<String> =
+            HashSet::from(["data/a.txt".to_string(), "data/sub/b.txt".to_string()]);
+
+        let without_slash: HashSet<String> = rocrate
+            .entities_under_directory("data")
+            .into_iter()
+            .map(|graph_vector| graph_vector.get_id().clone())
+            .collect();
+        let with_slash: HashSet<String> = rocrate
+            .entities_under_directory("data/")
+            .into_iter()
+            .map(|graph_vector| graph_vector.get_id().clone())
+            .collect();
+
+        assert_eq!(without_slash, expected);
+        assert_eq!(with_slash, expected);
+    }
+
+    #[test]
+    fn missing_has_part_entries_flags_only_unreferenced_entities() {
+        let mut rocrate = empty_crate();
+        rocrate
+            .graph
+            .push(directory_entity_with_has_part("data/", vec!["data/a.txt"]));
+        rocrate.graph.push(file_entity("data/a.txt"));
+        rocrate.graph.push(file_entity("data/sub/b.txt"));
+        rocrate.graph.push(file_entity("database/other.txt"));
+
+        let missing = rocrate.missing_has_part_entries("data");
+
+        assert_eq!(missing, vec!["data/sub/b.txt".to_string()]);
+    }
+
+    #[test]
+    fn missing_has_part_entries_empty_when_fully_listed() {
+        let mut rocrate = empty_crate();
+        rocrate.graph.push(directory_entity_with_has_part(
+            "data/",
+            vec!["data/a.txt", "data/sub/b.txt"],
+        ));
+        rocrate.graph.push(file_entity("data/a.txt"));
+        rocrate.graph.push(file_entity("data/sub/b.txt"));
+
+        assert!(rocrate.missing_has_part_entries("data/").is_empty());
+    }
+}
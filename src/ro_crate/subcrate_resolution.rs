@@ -1,9 +1,12 @@
 use crate::ro_crate::constraints::Id;
 use crate::ro_crate::data_entity::DataEntity;
+use crate::ro_crate::graph_vector::GraphVector;
 use crate::ro_crate::rocrate::RoCrate;
 use crate::ro_crate::write::is_not_url;
 use log::{debug, warn};
 use reqwest::header::{HeaderMap, ToStrError};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{HashMap, HashSet};
 use std::io::{Bytes, Cursor, Read};
 use zip::result::ZipError;
 use zip::ZipArchive;
@@ -17,6 +20,21 @@ pub enum FetchError {
     ZipError(ZipError),
     IoError(std::io::Error),
     SerializationError(serde_json::Error),
+    PoolError(String),
+    ChecksumMismatch {
+        id: String,
+        algorithm: &'static str,
+        expected: String,
+        actual: String,
+    },
+    /// A [`FetchConfig`] cap was hit during [`fetch_subcrates_recursive`] -
+    /// `kind` is `"subcrates"` or `"bytes"`, naming which of
+    /// `max_subcrates`/`max_total_bytes` was reached. Reaching `max_depth` stops
+    /// the descent without an error.
+    LimitExceeded {
+        kind: &'static str,
+        limit: usize,
+    },
 }
 impl std::fmt::Display for FetchError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -42,6 +60,23 @@ impl std::fmt::Display for FetchError {
             FetchError::SerializationError(err) => {
                 write!(f, "Serialization error `{}`", err)
             }
+            FetchError::PoolError(err) => {
+                write!(f, "Thread pool error: `{}`", err)
+            }
+            FetchError::LimitExceeded { kind, limit } => {
+                write!(f, "Fetch limit exceeded: {kind} capped at {limit}")
+            }
+            FetchError::ChecksumMismatch {
+                id,
+                algorithm,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "{algorithm} mismatch fetching `{id}`: expected `{expected}`, got `{actual}`"
+                )
+            }
         }
     }
 }
@@ -74,74 +109,324 @@ impl From<serde_json::Error> for FetchError {
     }
 }
 
-pub fn fetch_subcrates(rocrate: RoCrate) -> Result<Vec<RoCrate>, FetchError> {
-    let subcrates = rocrate.get_subcrates();
+/// How strictly a fetched subcrate's bytes are checked against the digest the
+/// parent crate declared for it (see [`expected_digest`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyMode {
+    /// Don't compute or compare a digest at all.
+    #[default]
+    Off,
+    /// Compare the digest, but only log a mismatch rather than failing the fetch.
+    WarnOnly,
+    /// Fail the fetch with [`FetchError::ChecksumMismatch`] on a mismatch.
+    Strict,
+}
+
+/// Resolves a single subcrate entity to its `RoCrate`, returning the id it was
+/// resolved under alongside it so callers that need to key off it (the
+/// traversal in [`fetch_subcrates_recursive`]) don't have to recompute it.
+fn resolve_subcrate(
+    subcrate: &DataEntity,
+    verify: VerifyMode,
+) -> Result<(String, RoCrate), FetchError> {
+    let id = get_id(subcrate);
+    let resolved = if is_not_url(&id) {
+        try_resolve_local(&id)?
+    } else {
+        try_resolve_remote(&id, subcrate, verify, usize::MAX)?.0
+    };
+    Ok((id, resolved))
+}
+
+/// Same resolution as [`resolve_subcrate`], but consulting `cache` before
+/// issuing the GET and populating it afterwards - the cache-aware counterpart
+/// threaded through [`fetch_subcrates_recursive`]. Also returns how many bytes
+/// were spent resolving this subcrate - the actual body size read off the
+/// wire for a fresh fetch, or the re-serialised size for a cache hit or local
+/// read (neither of which has a response stream to bound) - so callers
+/// tracking a cumulative byte cap can charge it against `remaining_bytes`
+/// without re-measuring the result themselves.
+fn resolve_subcrate_cached(
+    subcrate: &DataEntity,
+    verify: VerifyMode,
+    cache: &mut Option<&mut dyn SubcrateCache>,
+    remaining_bytes: usize,
+) -> Result<(String, RoCrate, usize), FetchError> {
+    let id = get_id(subcrate);
 
+    if is_not_url(&id) {
+        let resolved = try_resolve_local(&id)?;
+        let size = serde_json::to_vec(&resolved).map(|v| v.len()).unwrap_or(0);
+        if size > remaining_bytes {
+            return Err(FetchError::LimitExceeded {
+                kind: "bytes",
+                limit: remaining_bytes,
+            });
+        }
+        return Ok((id.clone(), resolved, size));
+    }
+
+    if let Some(cache) = cache.as_deref_mut() {
+        if let Some(cached) = cache.get(&id, remaining_bytes) {
+            let size = serde_json::to_vec(&cached).map(|v| v.len()).unwrap_or(0);
+            if size > remaining_bytes {
+                return Err(FetchError::LimitExceeded {
+                    kind: "bytes",
+                    limit: remaining_bytes,
+                });
+            }
+            return Ok((id, cached, size));
+        }
+    }
+
+    let (resolved, headers, bytes_read) =
+        try_resolve_remote(&id, subcrate, verify, remaining_bytes)?;
+    if let Some(cache) = cache.as_deref_mut() {
+        cache.put(&id, &resolved, &headers);
+    }
+    Ok((id, resolved, bytes_read))
+}
+
+pub fn fetch_subcrates(rocrate: &RoCrate, verify: VerifyMode) -> Result<Vec<RoCrate>, FetchError> {
     let mut collected_subcrates = Vec::new();
 
-    for graph_vector in subcrates {
+    for graph_vector in rocrate.get_subcrates() {
         let subcrate = match graph_vector {
-            crate::ro_crate::graph_vector::GraphVector::DataEntity(data_entity) => data_entity,
+            GraphVector::DataEntity(data_entity) => data_entity,
             _ => continue,
         };
 
-        // Try to find the subcrate id
-        let id = get_id(subcrate);
+        match resolve_subcrate(subcrate, verify) {
+            Ok((_, resolved)) => collected_subcrates.push(resolved),
+            Err(err) => warn!("{}", err),
+        }
+    }
 
-        if is_not_url(&id) {
+    Ok(collected_subcrates)
+}
 
-            match try_resolve_local(&id) {
-                Ok(rocrate) => {
-                    collected_subcrates.push(rocrate);
-                    continue;
-                }
-                Err(err) => warn!("{}", err),
-            }
-        } else {
-            match try_resolve_remote(&id) {
-                Ok(rocrate) => {
-                    collected_subcrates.push(rocrate);
-                    continue;
-                }
-                Err(err) => warn!("{}", err),
-            }
+/// Same resolution as [`fetch_subcrates`], but with up to `max_in_flight` HTTP
+/// requests issued concurrently on a bounded thread pool instead of one at a
+/// time. Results stay in the same order as `rocrate.get_subcrates()`, and a
+/// failure to resolve one subcrate doesn't stop the others from being fetched
+/// - each slot keeps its own `Result` rather than collapsing to a single
+/// batch-wide error.
+#[cfg(feature = "rayon")]
+pub fn fetch_subcrates_parallel(
+    rocrate: &RoCrate,
+    verify: VerifyMode,
+    max_in_flight: usize,
+) -> Result<Vec<Result<RoCrate, FetchError>>, FetchError> {
+    use rayon::prelude::*;
+
+    let subcrates: Vec<&DataEntity> = rocrate
+        .get_subcrates()
+        .into_iter()
+        .filter_map(|graph_vector| match graph_vector {
+            GraphVector::DataEntity(data_entity) => Some(data_entity),
+            _ => None,
+        })
+        .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(max_in_flight)
+        .build()
+        .map_err(|e| FetchError::PoolError(e.to_string()))?;
+
+    let results = pool.install(|| {
+        subcrates
+            .par_iter()
+            .map(|subcrate| resolve_subcrate(subcrate, verify).map(|(_, resolved)| resolved))
+            .collect()
+    });
+
+    Ok(results)
+}
+
+#[cfg(not(feature = "rayon"))]
+pub fn fetch_subcrates_parallel(
+    rocrate: &RoCrate,
+    verify: VerifyMode,
+    max_in_flight: usize,
+) -> Result<Vec<Result<RoCrate, FetchError>>, FetchError> {
+    let _ = max_in_flight;
+
+    let results = rocrate
+        .get_subcrates()
+        .into_iter()
+        .filter_map(|graph_vector| match graph_vector {
+            GraphVector::DataEntity(data_entity) => Some(data_entity),
+            _ => None,
+        })
+        .map(|subcrate| resolve_subcrate(subcrate, verify).map(|(_, resolved)| resolved))
+        .collect();
+
+    Ok(results)
+}
+
+/// Resolves a subcrate `id` that [`is_not_url`] identified as a local filesystem path
+/// rather than a URL - either pointing directly at a `ro-crate-metadata.json` file, or at
+/// a directory containing one.
+fn try_resolve_local(id: &str) -> Result<RoCrate, FetchError> {
+    let mut path = std::path::PathBuf::from(id);
+    if path.is_dir() {
+        path = path.join("ro-crate-metadata.json");
+    }
+    let contents = std::fs::read(&path)?;
+    Ok(serde_json::from_slice(&contents)?)
+}
+
+/// The digest (and which algorithm) the parent crate declared for a subcrate, read
+/// off `sha512`/`sha256` dynamic properties on the entity referencing it - `sha512`
+/// is preferred when both are present. A digest declared only on the entity's
+/// `distribution`/`subjectOf` node is not read.
+fn expected_digest(entity: &DataEntity) -> Option<(&'static str, String)> {
+    let dynamic_entities = entity.dynamic_entity.as_ref()?;
+    if let Some(value) = dynamic_entities.get("sha512") {
+        return Some(("sha512", value.to_string()));
+    }
+    if let Some(value) = dynamic_entities.get("sha256") {
+        return Some(("sha256", value.to_string()));
+    }
+    None
+}
+
+fn digest_for(algorithm: &str, body: &[u8]) -> String {
+    match algorithm {
+        "sha512" => format!("{:x}", Sha512::digest(body)),
+        _ => format!("{:x}", Sha256::digest(body)),
+    }
+}
+
+fn verify_body(
+    id: &str,
+    entity: &DataEntity,
+    body: &[u8],
+    verify: VerifyMode,
+) -> Result<(), FetchError> {
+    if verify == VerifyMode::Off {
+        return Ok(());
+    }
+    let Some((algorithm, expected)) = expected_digest(entity) else {
+        return Ok(());
+    };
+    let actual = digest_for(algorithm, body);
+    if actual.eq_ignore_ascii_case(&expected) {
+        return Ok(());
+    }
+
+    match verify {
+        VerifyMode::Off => Ok(()),
+        VerifyMode::WarnOnly => {
+            warn!("{algorithm} mismatch fetching `{id}`: expected `{expected}`, got `{actual}`");
+            Ok(())
         }
+        VerifyMode::Strict => Err(FetchError::ChecksumMismatch {
+            id: id.to_string(),
+            algorithm,
+            expected,
+            actual,
+        }),
     }
+}
 
-    Ok(vec![])
+/// Whether a response's `Content-Type` names an archive format rather than a
+/// bare JSON-LD document, so [`try_resolve_remote`] can skip the direct
+/// JSON-LD parse instead of wasting a parse attempt on bytes that are
+/// never going to deserialize as JSON.
+fn is_archive_content_type(headers: &HeaderMap) -> bool {
+    headers
+        .get("Content-Type")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            ["application/zip", "application/x-tar", "gzip", "bzip2", "zstd"]
+                .iter()
+                .any(|marker| content_type.contains(marker))
+        })
 }
 
-fn try_resolve_local(id: &str) -> Result<RoCrate, FetchError> {
-    todo!()
+/// Reads `response`'s body in fixed-size chunks, checking the running total against
+/// `budget` after each one instead of buffering the whole response before finding out it
+/// was too big. Exceeding `budget` stops reading - and so downloading - immediately, rather
+/// than letting a multi-gigabyte body land fully in memory (and on the wire) before
+/// [`walk_subcrates`] gets a chance to reject it.
+fn read_capped_body(mut response: reqwest::blocking::Response, budget: usize) -> Result<Vec<u8>, FetchError> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    let mut body = Vec::new();
+    let mut chunk = [0u8; CHUNK_SIZE];
+    loop {
+        let read = response.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+        if body.len() > budget {
+            return Err(FetchError::LimitExceeded {
+                kind: "bytes",
+                limit: budget,
+            });
+        }
+    }
+    Ok(body)
 }
 
-fn try_resolve_remote(id: &str) -> Result<RoCrate, FetchError> {
-    let response = reqwest::blocking::get(id)?;
+/// Resolves `id` to a `RoCrate`, also returning the response headers (so a
+/// [`SubcrateCache`] can be populated with its `ETag`/`Last-Modified` validators) and the
+/// number of body bytes read. The request prefers JSON-LD via `Accept` (falling back to
+/// whatever the server actually sends) and, like any `reqwest::blocking` request, follows
+/// redirects before `id` is resolved to a final URL.
+///
+/// `budget` bounds how many bytes of the primary response body [`read_capped_body`] will
+/// read before giving up - callers tracking a cumulative cap (like [`walk_subcrates`]) pass
+/// in whatever's left of it, so a single oversized subcrate is rejected mid-download rather
+/// than after it's been fully fetched and parsed.
+fn try_resolve_remote(
+    id: &str,
+    entity: &DataEntity,
+    verify: VerifyMode,
+    budget: usize,
+) -> Result<(RoCrate, HeaderMap, usize), FetchError> {
+    let response = reqwest::blocking::Client::new()
+        .get(id)
+        .header(
+            "Accept",
+            "application/ld+json, application/json;q=0.9, */*;q=0.8",
+        )
+        .send()?;
     let headers = response.headers().clone();
     let redirect_url = response.url().to_string();
-    let body = response.bytes()?;
+    let body = read_capped_body(response, budget)?;
+    let body_len = body.len();
 
-    if let Ok(ro_crate) = serde_json::from_slice::<RoCrate>(&body) {
-        return Ok(ro_crate);
+    verify_body(id, entity, &body, verify)?;
+
+    if !is_archive_content_type(&headers) {
+        if let Ok(ro_crate) = serde_json::from_slice::<RoCrate>(&body) {
+            return Ok((ro_crate, headers, body_len));
+        }
     }
 
-    if let Ok(ro_crate) = try_signposting(&headers) {
-        return Ok(ro_crate);
+    if let Ok(ro_crate) = try_signposting(&headers, id, budget) {
+        return Ok((ro_crate, headers, body_len));
     }
 
-    if let Ok(ro_crate) = try_content_negotiation(&id) {
-        return Ok(ro_crate);
+    if let Ok(ro_crate) = try_content_negotiation(&id, budget) {
+        return Ok((ro_crate, headers, body_len));
     }
 
-    if let Ok(ro_crate) = guess_location(&redirect_url) {
-        return Ok(ro_crate);
+    if let Ok(ro_crate) = guess_location(&redirect_url, budget) {
+        return Ok((ro_crate, headers, body_len));
     }
 
-    if let Ok(ro_crate) = try_zip(&headers, &redirect_url) {
-        return Ok(ro_crate);
+    if let Ok(ro_crate) = try_archive(&headers, &redirect_url, budget) {
+        return Ok((ro_crate, headers, body_len));
     }
+
     Err(FetchError::NotFound(format!(
-        "Could not retrieve subcrate with id {id}"
+        "Could not retrieve subcrate with id {id}: tried direct JSON-LD, \
+         signposting, content negotiation, a guessed `ro-crate-metadata.json` \
+         location, and archive extraction - no `ro-crate-metadata.json` found \
+         via any of them"
     )))
 }
 
@@ -175,22 +460,107 @@ fn get_id(entity: &DataEntity) -> String {
     id
 }
 
-fn try_signposting(headers: &HeaderMap) -> Result<RoCrate, FetchError> {
-    // 1. **signposting** to id and look for Link with `rel="describedBy"`
+/// A FAIR Signposting linkset document, as returned by a `rel="linkset"` link.
+///
+/// A single request, rather than one `Link` header per relation. Each member
+/// describes the signposting relations for one `anchor` resource; `fetch_subcrates`
+/// only cares about the member whose anchor matches the subcrate it requested.
+#[derive(Debug, serde::Deserialize)]
+struct LinksetDocument {
+    linkset: Vec<LinksetMember>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LinksetMember {
+    anchor: String,
+    #[serde(default)]
+    describedby: Vec<LinksetEntry>,
+    #[serde(default)]
+    item: Vec<LinksetEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LinksetEntry {
+    href: String,
+    #[serde(default)]
+    r#type: Option<String>,
+}
+
+/// Picks the best href out of a relation's entries: one typed `application/ld+json`
+/// (or the legacy `application/json+ld`) if there is one, else whatever comes first.
+fn pick_ld_json_href(entries: &[LinksetEntry]) -> Option<String> {
+    entries
+        .iter()
+        .find(|entry| {
+            matches!(
+                entry.r#type.as_deref(),
+                Some("application/ld+json") | Some("application/json+ld")
+            )
+        })
+        .or_else(|| entries.first())
+        .map(|entry| entry.href.clone())
+}
+
+/// Fetches and resolves a `rel="linkset"` document down to the RO-Crate metadata
+/// for `anchor`, preferring its `describedby` relation over `item`. Both requests'
+/// bodies are read through [`read_capped_body`] against `budget`, same as the primary
+/// subcrate fetch.
+fn resolve_linkset(linkset_url: &str, anchor: &str, budget: usize) -> Result<RoCrate, FetchError> {
+    let linkset_body = read_capped_body(reqwest::blocking::get(linkset_url)?, budget)?;
+    let document: LinksetDocument = serde_json::from_slice(&linkset_body)?;
+    let member = document
+        .linkset
+        .into_iter()
+        .find(|member| member.anchor == anchor)
+        .ok_or_else(|| {
+            FetchError::NotFound(format!("No linkset member found for anchor {anchor}"))
+        })?;
+
+    let href = pick_ld_json_href(&member.describedby)
+        .or_else(|| pick_ld_json_href(&member.item))
+        .ok_or_else(|| {
+            FetchError::NotFound(format!("No describedby/item entry in linkset for {anchor}"))
+        })?;
+
+    let body = read_capped_body(reqwest::blocking::get(&href)?, budget)?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// `budget` bounds every request this makes, same as [`try_resolve_remote`]'s own primary
+/// body read - each fallback is a fresh HTTP round trip and so gets its own capped read.
+fn try_signposting(headers: &HeaderMap, anchor: &str, budget: usize) -> Result<RoCrate, FetchError> {
+    // 1. **signposting linkset**: a single `rel="linkset"; type="application/linkset+json"`
+    //    link batches every relation for every anchor into one document, which FAIR
+    //    repositories like Zenodo prefer over individual Link headers.
+    for link in headers.get_all("Link") {
+        let values = link.to_str()?.to_string();
+        if values.contains("rel=\"linkset\"") && values.contains("application/linkset+json") {
+            if let Some((link, _)) = values.split_once(";") {
+                let url = link.replace("<", "").replace(">", "");
+                if let Ok(rocrate) = resolve_linkset(&url, anchor, budget) {
+                    return Ok(rocrate);
+                }
+            }
+        }
+    }
+
+    // 2. **per-relation signposting**: look for Link with `rel="describedBy"`
     //    or `rel="item"` and prefer links for both where `profile="https://w3id.org/ro/crate`
     for link in headers.get_all("Link") {
         let values = link.to_str()?.to_string();
         if values.contains("profile=\"https://w3id.org/ro/crate\"") {
             if let Some((link, _)) = values.split_once(";") {
                 let url = link.replace("<", "").replace(">", "");
-                let rocrate: RoCrate = reqwest::blocking::get(&url)?.json()?;
+                let body = read_capped_body(reqwest::blocking::get(&url)?, budget)?;
+                let rocrate: RoCrate = serde_json::from_slice(&body)?;
                 return Ok(rocrate);
             }
         } else {
             if values.contains("rel=\"describedBy\"") || values.contains("rel=\"item\"") {
                 if let Some((link, _)) = values.split_once(";") {
                     let url = link.replace("<", "").replace(">", "");
-                    let rocrate: RoCrate = reqwest::blocking::get(&url)?.json()?;
+                    let body = read_capped_body(reqwest::blocking::get(&url)?, budget)?;
+                    let rocrate: RoCrate = serde_json::from_slice(&body)?;
                     return Ok(rocrate);
                 }
             }
@@ -199,7 +569,7 @@ fn try_signposting(headers: &HeaderMap) -> Result<RoCrate, FetchError> {
     Err(FetchError::NotFound("No valid rocrate found".to_string()))
 }
 
-fn try_content_negotiation(id: &str) -> Result<RoCrate, FetchError> {
+fn try_content_negotiation(id: &str, budget: usize) -> Result<RoCrate, FetchError> {
     // 2. **content negotiation** with accept header `application/ld+json;profile=https://w3id.org/ro/crate`
     let content_negotiation_response = reqwest::blocking::Client::new()
         .get(id)
@@ -209,10 +579,11 @@ fn try_content_negotiation(id: &str) -> Result<RoCrate, FetchError> {
         )
         .send()?;
 
-    Ok(content_negotiation_response.json::<RoCrate>()?)
+    let body = read_capped_body(content_negotiation_response, budget)?;
+    Ok(serde_json::from_slice(&body)?)
 }
 
-fn guess_location(redirect_url: &str) -> Result<RoCrate, FetchError> {
+fn guess_location(redirect_url: &str, budget: usize) -> Result<RoCrate, FetchError> {
     // 3. **basically guess**: If PID `https://w3id.org/workflowhub/workflow-ro-crate/1.0`
     //    redirects to `https://about.workflowhub.eu/Workflow-RO-Crate/1.0/index.html`
     //    then try `https://about.workflowhub.eu/Workflow-RO-Crate/1.0/ro-crate-metadata.json`
@@ -227,54 +598,706 @@ fn guess_location(redirect_url: &str) -> Result<RoCrate, FetchError> {
     };
     let content_negotiation_response = reqwest::blocking::Client::new().get(guessed_url).send()?;
 
-    Ok(content_negotiation_response.json::<RoCrate>()?)
+    let body = read_capped_body(content_negotiation_response, budget)?;
+    Ok(serde_json::from_slice(&body)?)
 }
 
-fn try_zip(headers: &HeaderMap, redirect_url: &str) -> Result<RoCrate, FetchError> {
+/// Which container/compression a subcrate distribution body is wrapped in.
+///
+/// Trusting `Content-Type` alone falls over on repositories that serve
+/// everything as `application/octet-stream`, so [`sniff_archive_kind`] also
+/// checks the leading magic bytes of the body itself as a fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Gzip,
+    Bzip2,
+    Zstd,
+    Unknown,
+}
+
+fn sniff_archive_kind(content_type: Option<&str>, body: &[u8]) -> ArchiveKind {
+    if let Some(content_type) = content_type {
+        if content_type.contains("application/zip") {
+            return ArchiveKind::Zip;
+        }
+        if content_type.contains("gzip") {
+            return ArchiveKind::Gzip;
+        }
+        if content_type.contains("bzip2") {
+            return ArchiveKind::Bzip2;
+        }
+        if content_type.contains("zstd") {
+            return ArchiveKind::Zstd;
+        }
+    }
+
+    match body {
+        [0x50, 0x4b, 0x03, 0x04, ..] => ArchiveKind::Zip,
+        [0x1f, 0x8b, ..] => ArchiveKind::Gzip,
+        [0x42, 0x5a, 0x68, ..] => ArchiveKind::Bzip2,
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => ArchiveKind::Zstd,
+        _ => ArchiveKind::Unknown,
+    }
+}
+
+/// 4. If the retrieved resource is a ZIP, gzip/bzip2/zstd-compressed tarball
+///    (by `Content-Type` or magic bytes), extract `ro-crate-metadata.json` from
+///    the archive root or one directory deep, exactly like the BagIt path does.
+fn try_archive(headers: &HeaderMap, redirect_url: &str, budget: usize) -> Result<RoCrate, FetchError> {
+    let content_type = headers
+        .get("Content-Type")
+        .map(|value| value.to_str())
+        .transpose()?;
+    let body = read_capped_body(reqwest::blocking::get(redirect_url)?, budget)?;
+
+    match sniff_archive_kind(content_type, &body) {
+        ArchiveKind::Zip => extract_from_zip(&body),
+        ArchiveKind::Gzip => extract_from_tar(tar::Archive::new(flate2::read::GzDecoder::new(
+            std::io::Cursor::new(body),
+        ))),
+        ArchiveKind::Bzip2 => extract_from_bzip2(&body),
+        ArchiveKind::Zstd => extract_from_zstd(&body),
+        ArchiveKind::Unknown => Err(FetchError::NotFound("No subcrate found".to_string())),
+    }
+}
+
+#[cfg(feature = "bzip2")]
+fn extract_from_bzip2(body: &[u8]) -> Result<RoCrate, FetchError> {
+    extract_from_tar(tar::Archive::new(bzip2::read::BzDecoder::new(
+        std::io::Cursor::new(body),
+    )))
+}
+
+#[cfg(not(feature = "bzip2"))]
+fn extract_from_bzip2(_body: &[u8]) -> Result<RoCrate, FetchError> {
+    Err(FetchError::NotFound(
+        "bzip2 distribution requires the `bzip2` feature to be enabled".to_string(),
+    ))
+}
+
+#[cfg(feature = "zstd")]
+fn extract_from_zstd(body: &[u8]) -> Result<RoCrate, FetchError> {
+    let decoder = zstd::stream::read::Decoder::new(std::io::Cursor::new(body))?;
+    extract_from_tar(tar::Archive::new(decoder))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn extract_from_zstd(_body: &[u8]) -> Result<RoCrate, FetchError> {
+    Err(FetchError::NotFound(
+        "zstd distribution requires the `zstd` feature to be enabled".to_string(),
+    ))
+}
+
+/// Walks a (possibly nested-one-level) tar's entries looking for
+/// `ro-crate-metadata.json`, streaming decompression rather than buffering the
+/// whole extracted archive in memory.
+fn extract_from_tar<R: Read>(mut archive: tar::Archive<R>) -> Result<RoCrate, FetchError> {
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let depth = path.split('/').filter(|part| !part.is_empty()).count();
+        if depth <= 2 && path.ends_with("ro-crate-metadata.json") {
+            let mut buffer = Vec::new();
+            entry.read_to_end(&mut buffer)?;
+            return Ok(serde_json::from_slice(&buffer)?);
+        }
+    }
+    Err(FetchError::NotFound("No subcrate found".to_string()))
+}
+
+fn extract_from_zip(body: &[u8]) -> Result<RoCrate, FetchError> {
     // 4. If retrieved resource has `Content-Type: application/zip` or is a ZIP file
     //    extract ro-crate-metadata.json or if only contains single folder, extract
     //    folder/ro-crate-metadata.json
-    if let Some(content_type) = headers.get("Content-Type") {
-        if content_type.to_str()?.contains("application/zip") {
-            let response = reqwest::blocking::get(redirect_url)?.bytes()?;
-            let reader = std::io::Cursor::new(response);
-            let mut archive = ZipArchive::new(reader)?;
+    let reader = std::io::Cursor::new(body);
+    let mut archive = ZipArchive::new(reader)?;
+
+    // Retrieve the file by name
+    if let Ok(mut file_in_zip) = archive.by_name("ro-crate-metadata.json") {
+        // Read the file contents into memory
+        let mut buffer = Vec::new();
+        file_in_zip.read_to_end(&mut buffer)?;
+
+        let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
+
+        return Ok(subcrate);
+    }
+    if let Ok(mut bagit) = archive.by_name("bagit.txt") {
+        // 5. If retrieved resource is a BagIt archive, extract and verify checksums,
+        //    then return data/ro-crate-metdata.json
+        let mut buffer = Vec::new();
+        bagit.read_to_end(&mut buffer)?;
+
+        let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
+        return Ok(subcrate);
+    }
+    // Handle directories
+    let names: Vec<String> = archive.file_names().map(|e| e.to_string()).collect();
+    if let Some(bagit) = names.iter().find(|x| x.contains("bagit.txt")) {
+        let prefix = bagit.strip_suffix("bagit.txt").unwrap_or("");
+        let payload_path = format!("{prefix}data/ro-crate-metadata.json");
+        if let Ok(mut file_in_zip) = archive.by_name(&payload_path) {
+            let mut buffer = Vec::new();
+            file_in_zip.read_to_end(&mut buffer)?;
+            return Ok(serde_json::from_slice(&buffer)?);
+        }
+        return Err(FetchError::NotFound(format!(
+            "BagIt archive `{bagit}` has no `data/ro-crate-metadata.json` payload"
+        )));
+    }
+    if let Some(rocrate) = names.iter().find(|x| x.contains("metadata.json")) {
+        let mut file_in_zip = archive.by_name(rocrate)?;
 
-            // Retrieve the file by name
-            if let Ok(mut file_in_zip) = archive.by_name("ro-crate-metadata.json") {
-                // Read the file contents into memory
-                let mut buffer = Vec::new();
-                file_in_zip.read_to_end(&mut buffer)?;
+        let mut buffer = Vec::new();
+        file_in_zip.read_to_end(&mut buffer)?;
 
-                let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
+        let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
 
-                return Ok(subcrate);
+        return Ok(subcrate);
+    }
+    Err(FetchError::NotFound("No subcrate found".to_string()))
+}
+
+// Profile resolution, modelled on the RO-Crate Profiles Vocabulary: a profile is
+// just an entity in the graph that other profiles/crates can point back at via
+// `isProfileOf`/`isTransitiveProfileOf` (or, for a crate's root, `conformsTo`), so
+// a crate conforming to a specialised profile is recognised as also conforming to
+// that profile's ancestors.
+
+fn dynamic_entity_of(entity: &GraphVector) -> Option<&std::collections::HashMap<String, crate::ro_crate::modify::DynamicEntity>> {
+    match entity {
+        GraphVector::MetadataDescriptor(e) => e.dynamic_entity.as_ref(),
+        GraphVector::RootDataEntity(e) => e.dynamic_entity.as_ref(),
+        GraphVector::DataEntity(e) => e.dynamic_entity.as_ref(),
+        GraphVector::ContextualEntity(e) => e.dynamic_entity.as_ref(),
+    }
+}
+
+/// Reads a dynamic property as a set of referenced `@id`s, splitting on comma the
+/// same way multi-valued properties are joined elsewhere in this crate.
+fn referenced_ids(entity: &GraphVector, property: &str) -> Vec<String> {
+    let Some(dynamic_entity) = dynamic_entity_of(entity) else {
+        return Vec::new();
+    };
+    let Some(value) = dynamic_entity.get(property) else {
+        return Vec::new();
+    };
+    value
+        .to_string()
+        .split(',')
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// A profile's direct parents: the other profiles it extends, via either
+/// `isProfileOf` or `isTransitiveProfileOf`.
+fn parent_profiles(entity: &GraphVector) -> Vec<String> {
+    let mut parents = referenced_ids(entity, "isProfileOf");
+    parents.extend(referenced_ids(entity, "isTransitiveProfileOf"));
+    parents
+}
+
+/// The profile URIs a crate's root data entity directly declares via `conformsTo`.
+fn root_conforms_to(rocrate: &RoCrate) -> Vec<String> {
+    for entity in &rocrate.graph {
+        if let GraphVector::RootDataEntity(_) = entity {
+            return referenced_ids(entity, "conformsTo");
+        }
+    }
+    Vec::new()
+}
+
+/// Computes the transitive closure of a crate's declared profiles: its root's
+/// `conformsTo`, plus - for each profile entity found in the graph - its own
+/// parent profiles, recursively. Profiles that aren't described anywhere in this
+/// crate's own graph (the common case for well-known profiles like
+/// `https://w3id.org/ro/crate`) are still included in the closure; they just have
+/// no further parents to walk.
+pub fn transitive_profiles(rocrate: &RoCrate) -> HashSet<String> {
+    let mut closure = HashSet::new();
+    let mut frontier = root_conforms_to(rocrate);
+
+    while let Some(uri) = frontier.pop() {
+        if !closure.insert(uri.clone()) {
+            continue;
+        }
+        if let Some(entity) = rocrate.get_entity(&uri) {
+            frontier.extend(parent_profiles(entity));
+        }
+    }
+    closure
+}
+
+/// Fetches subcrates exactly as [`fetch_subcrates`] does, then keeps only those
+/// whose transitive profile set contains `profile_uri` - e.g. pulling just the
+/// workflow-run-crate subcrates out of a larger aggregating crate.
+pub fn fetch_subcrates_with_profile(
+    rocrate: &RoCrate,
+    verify: VerifyMode,
+    profile_uri: &str,
+) -> Result<Vec<RoCrate>, FetchError> {
+    let subcrates = fetch_subcrates(rocrate, verify)?;
+    Ok(subcrates
+        .into_iter()
+        .filter(|subcrate| transitive_profiles(subcrate).contains(profile_uri))
+        .collect())
+}
+
+/// Normalises a subcrate identifier/URL for use as a visited-set/cache key: a
+/// trailing slash or differing letter case in the scheme/host shouldn't be
+/// enough to make two references to the same subcrate look distinct.
+fn normalise_subcrate_id(id: &str) -> String {
+    id.trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// The result of [`fetch_subcrates_recursive`]: every distinct subcrate reached
+/// during the traversal, keyed by its normalised identifier, plus the
+/// parent -> child edges describing how they reference each other. A flat map
+/// and edge list - rather than a tree - is what lets this represent cycles (e.g.
+/// a subcrate whose `isPartOf` points back at the root) without the traversal
+/// itself needing special-casing beyond "don't recurse into a visited id".
+#[derive(Debug, Default)]
+pub struct SubcrateGraph {
+    pub crates: HashMap<String, RoCrate>,
+    pub edges: Vec<(String, String)>,
+}
+
+/// Caps enforced during [`fetch_subcrates_recursive`] so a malicious or
+/// accidentally self-referential crate graph (two sibling subcrates sharing
+/// one `@id`, say) can't send the traversal into an unbounded fetch loop.
+/// `max_total_bytes` is checked incrementally as each subcrate's body comes
+/// off the wire (see [`read_capped_body`]), against whatever's left of the
+/// budget after earlier subcrates - so an oversized subcrate is rejected
+/// mid-download rather than after it's been fully fetched and parsed. A
+/// cache hit or local read has no stream to bound this way and is instead
+/// checked against the remaining budget by its re-serialised size.
+#[derive(Debug, Clone, Copy)]
+pub struct FetchConfig {
+    pub max_depth: usize,
+    pub max_subcrates: usize,
+    pub max_total_bytes: usize,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            max_depth: 8,
+            max_subcrates: 256,
+            max_total_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// A cache consulted before issuing a subcrate GET, and populated after
+/// resolving one, so repeated traversals of the same federated crate graph
+/// (the same DOI reachable from several branches of the tree) don't
+/// re-download subcrates that haven't changed. Implementations own any
+/// revalidation policy: [`DiskSubcrateCache::get`] issues its own conditional
+/// request against `url` using the `ETag`/`Last-Modified` it stored alongside
+/// the cached entry, so callers just see a hit or a miss. `budget` bounds
+/// how many bytes of a revalidation response `get` may read, same as the
+/// rest of a [`FetchConfig::max_total_bytes`]-bounded traversal.
+pub trait SubcrateCache {
+    fn get(&mut self, url: &str, budget: usize) -> Option<RoCrate>;
+    fn put(&mut self, url: &str, rocrate: &RoCrate, headers: &HeaderMap);
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        CacheValidators {
+            etag: headers
+                .get("ETag")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+            last_modified: headers
+                .get("Last-Modified")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+}
+
+/// The default [`SubcrateCache`]: each entry is two files under `dir`, keyed by
+/// the sha256 of the resolved URL - `<key>.json` holding the cached [`RoCrate`]
+/// and `<key>.meta.json` holding its [`CacheValidators`]. A request that fails
+/// outright (origin unreachable, timeout) falls back to serving the stale
+/// cached copy rather than failing the whole crawl over one transient error.
+pub struct DiskSubcrateCache {
+    dir: std::path::PathBuf,
+}
+
+impl DiskSubcrateCache {
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        DiskSubcrateCache { dir: dir.into() }
+    }
+
+    fn key_for(url: &str) -> String {
+        format!("{:x}", Sha256::digest(url.as_bytes()))
+    }
+
+    fn crate_path(&self, key: &str) -> std::path::PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn meta_path(&self, key: &str) -> std::path::PathBuf {
+        self.dir.join(format!("{key}.meta.json"))
+    }
+}
+
+impl SubcrateCache for DiskSubcrateCache {
+    fn get(&mut self, url: &str, budget: usize) -> Option<RoCrate> {
+        let key = Self::key_for(url);
+        let cached_bytes = std::fs::read(self.crate_path(&key)).ok()?;
+        let validators: CacheValidators = std::fs::read(self.meta_path(&key))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        let mut request = reqwest::blocking::Client::new().get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag.as_str());
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified.as_str());
+        }
+
+        match request.send() {
+            Ok(response) if response.status() == reqwest::StatusCode::NOT_MODIFIED => {
+                serde_json::from_slice(&cached_bytes).ok()
+            }
+            Ok(response) if response.status().is_success() => {
+                let headers = response.headers().clone();
+                let body = read_capped_body(response, budget).ok()?;
+                let rocrate: RoCrate = serde_json::from_slice(&body).ok()?;
+                self.put(url, &rocrate, &headers);
+                Some(rocrate)
+            }
+            _ => serde_json::from_slice(&cached_bytes).ok(),
+        }
+    }
+
+    fn put(&mut self, url: &str, rocrate: &RoCrate, headers: &HeaderMap) {
+        let key = Self::key_for(url);
+        let Ok(bytes) = serde_json::to_vec(rocrate) else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(&self.dir);
+        let _ = std::fs::write(self.crate_path(&key), bytes);
+
+        if let Ok(meta_bytes) = serde_json::to_vec(&CacheValidators::from_headers(headers)) {
+            let _ = std::fs::write(self.meta_path(&key), meta_bytes);
+        }
+    }
+}
+
+/// Resolves `rocrate`'s subcrates, then recurses into each of *their* subcrates
+/// in turn, subject to `config`'s depth/count/byte caps. Each distinct subcrate
+/// (by [`normalise_subcrate_id`]) is fetched at most once: the visited set
+/// doubles as the fetch cache, so a diamond-shaped reference graph doesn't
+/// trigger redundant HTTP/file reads, and a cycle back to an already-visited id
+/// (rather than looping forever) just becomes an edge with no further
+/// recursion.
+pub fn fetch_subcrates_recursive(
+    rocrate: &RoCrate,
+    root_id: &str,
+    verify: VerifyMode,
+    config: FetchConfig,
+    mut cache: Option<&mut dyn SubcrateCache>,
+) -> Result<SubcrateGraph, FetchError> {
+    let mut graph = SubcrateGraph::default();
+    let mut visited = HashSet::new();
+    visited.insert(normalise_subcrate_id(root_id));
+    let mut total_bytes = 0usize;
+
+    walk_subcrates(
+        rocrate,
+        root_id,
+        0,
+        verify,
+        config,
+        &mut graph,
+        &mut visited,
+        &mut total_bytes,
+        &mut cache,
+    )?;
+
+    Ok(graph)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk_subcrates(
+    rocrate: &RoCrate,
+    parent_id: &str,
+    depth: usize,
+    verify: VerifyMode,
+    config: FetchConfig,
+    graph: &mut SubcrateGraph,
+    visited: &mut HashSet<String>,
+    total_bytes: &mut usize,
+    cache: &mut Option<&mut dyn SubcrateCache>,
+) -> Result<(), FetchError> {
+    if depth >= config.max_depth {
+        return Ok(());
+    }
+
+    for graph_vector in rocrate.get_subcrates() {
+        let subcrate = match graph_vector {
+            GraphVector::DataEntity(data_entity) => data_entity,
+            _ => continue,
+        };
+
+        let id = get_id(subcrate);
+        let key = normalise_subcrate_id(&id);
+        graph.edges.push((parent_id.to_string(), id.clone()));
+
+        if visited.contains(&key) {
+            // Already resolved via an earlier edge (diamond reference), or a
+            // cycle back to an ancestor - either way, don't refetch or recurse.
+            continue;
+        }
+        visited.insert(key.clone());
+
+        // `visited` holds the root plus every subcrate reached so far, this one included;
+        // `graph.crates` only fills in as recursion unwinds, so it would undercount here.
+        if visited.len() - 1 > config.max_subcrates {
+            return Err(FetchError::LimitExceeded {
+                kind: "subcrates",
+                limit: config.max_subcrates,
+            });
+        }
+
+        let remaining_bytes = config.max_total_bytes.saturating_sub(*total_bytes);
+        let resolved = match resolve_subcrate_cached(subcrate, verify, cache, remaining_bytes) {
+            Ok((_, resolved, bytes_read)) => {
+                *total_bytes += bytes_read;
+                resolved
             }
-            if let Ok(mut bagit) = archive.by_name("bagit.txt") {
-                // 5. If retrieved resource is a BagIt archive, extract and verify checksums,
-                //    then return data/ro-crate-metdata.json
-                let mut buffer = Vec::new();
-                bagit.read_to_end(&mut buffer)?;
-
-                let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
-                return Ok(subcrate);
+            Err(FetchError::LimitExceeded { kind: "bytes", .. }) => {
+                return Err(FetchError::LimitExceeded {
+                    kind: "bytes",
+                    limit: config.max_total_bytes,
+                });
             }
-            // Handle directories
-            let names: Vec<String> = archive.file_names().map(|e| e.to_string()).collect();
-            if let Some(bagit) = names.iter().find(|x| x.contains("bagit.txt")) {
-                todo!("Handle bagit");
+            Err(err) => {
+                warn!("failed to fetch subcrate `{id}`: {err}");
+                continue;
             }
-            if let Some(rocrate) = names.iter().find(|x| x.contains("metadata.json")) {
-                let mut file_in_zip = archive.by_name(rocrate)?;
+        };
+
+        walk_subcrates(
+            &resolved,
+            &id,
+            depth + 1,
+            verify,
+            config,
+            graph,
+            visited,
+            total_bytes,
+            cache,
+        )?;
+        graph.crates.insert(key, resolved);
+    }
+    Ok(())
+}
+
+/// One wave of [`fetch_subcrates_recursive_async`]'s breadth-first traversal:
+/// either the root crate passed in by the caller (borrowed, never itself
+/// inserted into [`SubcrateGraph::crates`]) or a subcrate resolved on a
+/// previous wave (owned, inserted once its own children have been read off).
+#[cfg(feature = "rayon")]
+enum Wave<'a> {
+    Root(&'a RoCrate),
+    Resolved(RoCrate),
+}
+
+#[cfg(feature = "rayon")]
+impl Wave<'_> {
+    fn get_subcrates(&self) -> Vec<&GraphVector> {
+        match self {
+            Wave::Root(rocrate) => rocrate.get_subcrates(),
+            Wave::Resolved(rocrate) => rocrate.get_subcrates(),
+        }
+    }
+}
 
-                let mut buffer = Vec::new();
-                file_in_zip.read_to_end(&mut buffer)?;
+/// Same traversal as [`fetch_subcrates_recursive`], but level-by-level
+/// breadth-first rather than depth-first, with every subcrate *at a given
+/// level* resolved concurrently on a bounded thread pool (`max_concurrency`
+/// workers) instead of one HTTP round trip at a time. This codebase talks to
+/// the network through `reqwest::blocking` with no async runtime anywhere, so
+/// "concurrently" here means the same thread-pool-backed fan-out
+/// [`fetch_subcrates_parallel`] uses, not futures - the net effect (independent
+/// requests for one level in flight together, capped in number) is the same
+/// one a semaphore-bounded async crawl would give. `cache` is consulted and
+/// populated sequentially between waves rather than from the worker threads
+/// themselves, and each remote fetch in a wave is capped to whatever's left
+/// of `config.max_total_bytes` as of that wave's start.
+#[cfg(feature = "rayon")]
+pub fn fetch_subcrates_recursive_async(
+    rocrate: &RoCrate,
+    root_id: &str,
+    verify: VerifyMode,
+    config: FetchConfig,
+    max_concurrency: usize,
+    mut cache: Option<&mut dyn SubcrateCache>,
+) -> Result<SubcrateGraph, FetchError> {
+    use rayon::prelude::*;
 
-                let subcrate: RoCrate = serde_json::from_slice(&buffer)?;
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(max_concurrency)
+        .build()
+        .map_err(|e| FetchError::PoolError(e.to_string()))?;
 
-                return Ok(subcrate);
+    let mut graph = SubcrateGraph::default();
+    let mut visited = HashSet::new();
+    visited.insert(normalise_subcrate_id(root_id));
+    let mut total_bytes = 0usize;
+
+    let mut wave: Vec<(String, Wave)> = vec![(root_id.to_string(), Wave::Root(rocrate))];
+    let mut depth = 0;
+
+    while depth < config.max_depth && !wave.is_empty() {
+        let mut to_fetch = Vec::new();
+        for (parent_id, source) in &wave {
+            for graph_vector in source.get_subcrates() {
+                let subcrate = match graph_vector {
+                    GraphVector::DataEntity(data_entity) => data_entity,
+                    _ => continue,
+                };
+                let id = get_id(subcrate);
+                let key = normalise_subcrate_id(&id);
+                graph.edges.push((parent_id.clone(), id.clone()));
+                if visited.contains(&key) {
+                    // Already resolved via an earlier edge, or de-duplicated
+                    // against another in-flight request for the same id.
+                    continue;
+                }
+                visited.insert(key.clone());
+                to_fetch.push((key, id, subcrate));
             }
         }
+
+        // `visited` holds the root plus every subcrate queued so far, including the
+        // previous wave's, which only reach `graph.crates` further down this loop.
+        if visited.len() - 1 > config.max_subcrates {
+            return Err(FetchError::LimitExceeded {
+                kind: "subcrates",
+                limit: config.max_subcrates,
+            });
+        }
+
+        // Local ids and cache hits are resolved sequentially, outside the pool: neither
+        // involves a download worth parallelising, and a `SubcrateCache` isn't assumed
+        // thread-safe enough to share across worker threads. That leaves only genuine
+        // remote misses to fetch concurrently below.
+        let mut resolved: Vec<(String, Result<RoCrate, FetchError>)> = Vec::new();
+        let mut to_fetch_remote = Vec::new();
+        for (_key, id, subcrate) in to_fetch {
+            if is_not_url(&id) {
+                resolved.push((id.clone(), try_resolve_local(&id)));
+                continue;
+            }
+
+            let remaining_bytes = config.max_total_bytes.saturating_sub(total_bytes);
+            if let Some(cache) = cache.as_deref_mut() {
+                if let Some(cached) = cache.get(&id, remaining_bytes) {
+                    resolved.push((id.clone(), Ok(cached)));
+                    continue;
+                }
+            }
+            to_fetch_remote.push((id, subcrate));
+        }
+
+        // Every remote fetch is capped to whatever's left of the budget as of the start
+        // of this wave, so no single subcrate can download past it - the same discipline
+        // `try_resolve_remote` applies in the sequential traversal, just snapshotted per
+        // wave instead of updated after every single fetch.
+        let remaining_bytes = config.max_total_bytes.saturating_sub(total_bytes);
+        let fetched: Vec<(String, Result<(RoCrate, HeaderMap, usize), FetchError>)> =
+            pool.install(|| {
+                to_fetch_remote
+                    .into_par_iter()
+                    .map(|(id, subcrate)| {
+                        let result = try_resolve_remote(&id, subcrate, verify, remaining_bytes);
+                        (id, result)
+                    })
+                    .collect()
+            });
+
+        for (id, result) in fetched {
+            match result {
+                Ok((resolved_crate, headers, _bytes_read)) => {
+                    if let Some(cache) = cache.as_deref_mut() {
+                        cache.put(&id, &resolved_crate, &headers);
+                    }
+                    resolved.push((id, Ok(resolved_crate)));
+                }
+                Err(err) => resolved.push((id, Err(err))),
+            }
+        }
+
+        // Every borrow into `wave`'s entries ended with the `pool.install` call
+        // above, so its resolved crates can now move into `graph.crates`.
+        for (parent_id, source) in wave {
+            if let Wave::Resolved(rocrate) = source {
+                graph.crates.insert(normalise_subcrate_id(&parent_id), rocrate);
+            }
+        }
+
+        let mut next_wave = Vec::new();
+        for (id, result) in resolved {
+            let resolved = match result {
+                Ok(resolved) => resolved,
+                Err(err) => {
+                    warn!("failed to fetch subcrate `{id}`: {err}");
+                    continue;
+                }
+            };
+
+            total_bytes += serde_json::to_vec(&resolved).map(|v| v.len()).unwrap_or(0);
+            if total_bytes > config.max_total_bytes {
+                return Err(FetchError::LimitExceeded {
+                    kind: "bytes",
+                    limit: config.max_total_bytes,
+                });
+            }
+
+            next_wave.push((id, Wave::Resolved(resolved)));
+        }
+
+        wave = next_wave;
+        depth += 1;
     }
-    Err(FetchError::NotFound("No subcrate found".to_string()))
+
+    // Anything left in `wave` when the loop exits (max depth reached) was
+    // resolved but never had a chance to contribute its own children - still
+    // record it.
+    for (parent_id, source) in wave {
+        if let Wave::Resolved(rocrate) = source {
+            graph.crates.insert(normalise_subcrate_id(&parent_id), rocrate);
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(not(feature = "rayon"))]
+pub fn fetch_subcrates_recursive_async(
+    rocrate: &RoCrate,
+    root_id: &str,
+    verify: VerifyMode,
+    config: FetchConfig,
+    max_concurrency: usize,
+    mut cache: Option<&mut dyn SubcrateCache>,
+) -> Result<SubcrateGraph, FetchError> {
+    let _ = max_concurrency;
+    fetch_subcrates_recursive(rocrate, root_id, verify, config, cache.as_deref_mut())
 }
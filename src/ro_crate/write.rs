@@ -1,16 +1,28 @@
 //! Module for writing RoCrate structures to file.
 //!
 //! Allows basic ro-crate-metadata.json file creation, as well as archiving
-//! via zip.
+//! via zip, tar, or tar.gz (see [`ArchiveFormat`]), and extracting a packaged
+//! archive back out to a directory tree (see [`unzip_crate`]).
 
+use crate::ro_crate::constraints::{DataType, DynamicEntity, EntityValue, Id};
+use crate::ro_crate::data_entity::DataEntity;
+use crate::ro_crate::graph_vector::GraphVector;
+use crate::ro_crate::modify::DynamicEntityManipulation;
 use crate::ro_crate::read::{read_crate, CrateReadError};
 use crate::ro_crate::rocrate::RoCrate;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use dirs;
-use log::{debug, error};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use glob;
+use log::{debug, error, warn};
+use sha2::{Digest, Sha256, Sha512};
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
+use tar::Header;
 use thiserror::Error;
 use url::Url;
 use walkdir::WalkDir;
@@ -36,6 +48,453 @@ pub enum WriteError {
     ContextError(String),
     #[error("Read Error: {0}")]
     ReadError(#[from] CrateReadError),
+    #[error("`{0}` is excluded by the match list but is a described data entity")]
+    ExcludedDescribedEntity(String),
+    #[error("`{0}` has no matching entry in the archive")]
+    NoSuchZipEntry(String),
+    #[error("root `hasPart` references `{0}`, but the archive has no matching entry")]
+    HasPartEntryMissing(String),
+}
+
+/// Whether a [`MatchRule`] includes or excludes the paths it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchType {
+    Include,
+    Exclude,
+}
+
+/// A single glob rule evaluated against a crate-relative path, modelled on pxar's
+/// `MatchEntry`.
+#[derive(Debug, Clone)]
+pub struct MatchRule {
+    pattern: glob::Pattern,
+    match_type: MatchType,
+}
+
+impl MatchRule {
+    pub fn new(pattern: &str, match_type: MatchType) -> Result<Self, WriteError> {
+        let pattern = glob::Pattern::new(pattern)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+        Ok(MatchRule {
+            pattern,
+            match_type,
+        })
+    }
+}
+
+/// An ordered list of include/exclude glob rules, modelled on pxar's `MatchList`: rules
+/// are evaluated top-to-bottom against the crate-relative path of each walked file, with
+/// the last matching rule winning. Files that are described data entities in the
+/// metadata are force-included with a warning regardless of exclude rules, since excluding
+/// them would leave the crate internally inconsistent; when packaging with `strict` set,
+/// excluding one is an error instead.
+#[derive(Debug, Clone)]
+pub struct MatchList {
+    rules: Vec<MatchRule>,
+    default_include: bool,
+}
+
+impl MatchList {
+    /// Creates an empty match list with a default verdict applied when no rule matches -
+    /// `true` to include everything not otherwise excluded, `false` to exclude everything
+    /// not otherwise included.
+    pub fn new(default_include: bool) -> Self {
+        MatchList {
+            rules: Vec::new(),
+            default_include,
+        }
+    }
+
+    /// Appends a rule to the end of the list; later rules take priority over earlier
+    /// ones for any path they both match.
+    pub fn push(&mut self, pattern: &str, match_type: MatchType) -> Result<(), WriteError> {
+        self.rules.push(MatchRule::new(pattern, match_type)?);
+        Ok(())
+    }
+
+    /// Evaluates every rule against `relative_path`, crate-relative (e.g. `target/debug/foo`,
+    /// `.git/config`), returning the verdict of the last matching rule, or the list's
+    /// default if none match.
+    fn is_included(&self, relative_path: &Path) -> bool {
+        let mut included = self.default_include;
+        for rule in &self.rules {
+            if rule.pattern.matches_path(relative_path) {
+                included = rule.match_type == MatchType::Include;
+            }
+        }
+        included
+    }
+}
+
+/// A progress-reporting hook for `zip_crate`/`directory_walk`.
+///
+/// Packaging a large RO-Crate otherwise gives no feedback until the whole archive is
+/// finished. Implement this to wire up a progress bar (e.g. `indicatif`) without this
+/// crate taking that dependency itself; the no-callback path (`progress: None`) costs
+/// nothing extra.
+pub trait ZipProgress {
+    /// Called once, before any file is written, with a cheap first-pass count of the
+    /// files and total bytes the walk is about to archive.
+    fn on_start(&self, total_files: usize, total_bytes: u64);
+    /// Called after each file finishes copying into the archive.
+    fn on_file(&self, path: &Path, bytes_written: u64);
+    /// Called once the whole archive has been written.
+    fn on_finish(&self);
+}
+
+/// The archive container format [`zip_crate`] should write to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// The file extension (without a leading dot) conventionally used for this format.
+    fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Zip compression method selectable via [`CompressionOptions`]. Mirrors the subset of
+/// methods the `zip` crate supports, without leaking that dependency's own enum through
+/// this crate's public API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMethod {
+    Stored,
+    Deflated,
+    Bzip2,
+    Zstd,
+}
+
+/// Configures how [`zip_crate`] compresses a packaged archive. Only takes effect for
+/// [`ArchiveFormat::Zip`] - the tar-based formats have no per-entry compression of their
+/// own (wrap the whole archive in gzip via [`ArchiveFormat::TarGz`] instead).
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionOptions {
+    pub method: CompressionMethod,
+    /// Method-specific compression level, or `None` for the `zip` crate's default.
+    pub level: Option<i64>,
+    /// Whether to carry each entry's Unix file mode into the archive. When false, entries
+    /// are written with the zip's default permissions regardless of the mode passed to
+    /// [`ArchiveWriter::start_entry`].
+    pub preserve_permissions: bool,
+}
+
+impl Default for CompressionOptions {
+    /// `Deflated` at the crate's previous fixed behaviour: the default level, with
+    /// permissions preserved.
+    fn default() -> Self {
+        CompressionOptions {
+            method: CompressionMethod::Deflated,
+            level: None,
+            preserve_permissions: true,
+        }
+    }
+}
+
+impl CompressionOptions {
+    /// Checks that `method` was actually compiled into the linked `zip` crate. `Stored` and
+    /// `Deflated` are always available; `Bzip2` and `Zstd` are gated behind the `zip` crate's
+    /// own Cargo features of the same name, which this crate does not enable by default.
+    fn validate_method(self) -> Result<(), WriteError> {
+        match self.method {
+            CompressionMethod::Bzip2 if !cfg!(feature = "bzip2") => Err(
+                WriteError::ZipOperationError(
+                    "Bzip2 compression requires the `bzip2` feature to be enabled".to_string(),
+                ),
+            ),
+            CompressionMethod::Zstd if !cfg!(feature = "zstd") => Err(WriteError::ZipOperationError(
+                "Zstd compression requires the `zstd` feature to be enabled".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn to_zip_options(self) -> Result<SimpleFileOptions, WriteError> {
+        self.validate_method()?;
+
+        let method = match self.method {
+            CompressionMethod::Stored => zip::CompressionMethod::Stored,
+            CompressionMethod::Deflated => zip::CompressionMethod::Deflated,
+            CompressionMethod::Bzip2 => zip::CompressionMethod::Bzip2,
+            CompressionMethod::Zstd => zip::CompressionMethod::Zstd,
+        };
+
+        let mut options = SimpleFileOptions::default()
+            .compression_method(method)
+            .large_file(true);
+
+        if let Some(level) = self.level {
+            options = options.compression_level(Some(level));
+        }
+
+        if self.preserve_permissions {
+            options = options.unix_permissions(0o755);
+        }
+
+        Ok(options)
+    }
+}
+
+/// Configures whether [`zip_crate`] fingerprints each packaged file as it streams it into
+/// the archive. Off by default: hashing every byte of a large crate is not free, so it's
+/// opt-in rather than the fixed behaviour it used to be.
+///
+/// When enabled, each described [`crate::ro_crate::data_entity::DataEntity`] packaged from
+/// the crate directory gets its `contentSize`/`sha256` (and `sha512`/`blake3`, if requested)
+/// properties populated, and the archive gains a BagIt-style `manifest-sha256.txt` entry
+/// listing each such file's archive-relative path and SHA-256 digest, so the archive can be
+/// verified independently of the metadata it carries (see [`verify_archive_checksums`]).
+/// Files added under `external/` or `remote/` are neither fingerprinted nor listed in the
+/// manifest.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChecksumOptions {
+    pub enabled: bool,
+    /// Also compute a SHA-512 digest alongside the SHA-256 one. Ignored unless `enabled`.
+    pub sha512: bool,
+    /// Also compute a BLAKE3 digest alongside the SHA-256 one. Requires the `blake3`
+    /// feature; ignored unless `enabled`.
+    pub blake3: bool,
+}
+
+/// The digests a [`HashingReader`] produced once exhausted. `sha256` is present whenever
+/// [`ChecksumOptions::enabled`] was set; `sha512`/`blake3` only if additionally requested.
+struct FileDigests {
+    sha256: Option<String>,
+    sha512: Option<String>,
+    blake3: Option<String>,
+}
+
+/// Wraps a reader so that every byte read through it is folded into a running SHA-256
+/// (and optional SHA-512/BLAKE3) digest, letting [`directory_walk`] fingerprint a file in
+/// the same pass that reads it into memory for the archive, rather than reopening it afterwards.
+struct HashingReader<R> {
+    inner: R,
+    sha256: Option<Sha256>,
+    sha512: Option<Sha512>,
+    #[cfg(feature = "blake3")]
+    blake3: Option<blake3::Hasher>,
+}
+
+impl<R> HashingReader<R> {
+    fn new(inner: R, checksums: ChecksumOptions) -> Self {
+        HashingReader {
+            inner,
+            sha256: checksums.enabled.then(Sha256::new),
+            sha512: (checksums.enabled && checksums.sha512).then(Sha512::new),
+            #[cfg(feature = "blake3")]
+            blake3: (checksums.enabled && checksums.blake3).then(blake3::Hasher::new),
+        }
+    }
+
+    /// Consumes the reader, returning the finalised digests.
+    fn finalize(self) -> FileDigests {
+        FileDigests {
+            sha256: self.sha256.map(|hasher| format!("{:x}", hasher.finalize())),
+            sha512: self.sha512.map(|hasher| format!("{:x}", hasher.finalize())),
+            #[cfg(feature = "blake3")]
+            blake3: self.blake3.map(|hasher| hasher.finalize().to_hex().to_string()),
+            #[cfg(not(feature = "blake3"))]
+            blake3: None,
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        if bytes_read > 0 {
+            if let Some(hasher) = self.sha256.as_mut() {
+                hasher.update(&buf[..bytes_read]);
+            }
+            if let Some(hasher) = self.sha512.as_mut() {
+                hasher.update(&buf[..bytes_read]);
+            }
+            #[cfg(feature = "blake3")]
+            if let Some(hasher) = self.blake3.as_mut() {
+                hasher.update(&buf[..bytes_read]);
+            }
+        }
+        Ok(bytes_read)
+    }
+}
+
+/// Backend-agnostic write operations for packaging an RO-Crate, implemented by the
+/// existing zip backend ([`RoCrateZip`]) and the tar-based backends ([`TarArchiveWriter`]).
+/// `directory_walk` and `zip_crate_external` are written once against this trait, so the
+/// ID-remapping logic in both runs identically no matter which [`ArchiveFormat`] was
+/// chosen.
+pub trait ArchiveWriter {
+    /// Starts a new file entry named `name` with the given Unix file mode.
+    fn start_entry(&mut self, name: &str, mode: u32) -> Result<(), WriteError>;
+    /// Writes bytes into the entry most recently started with `start_entry`.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), WriteError>;
+    /// Adds an explicit directory entry named `name`.
+    fn add_directory(&mut self, name: &str) -> Result<(), WriteError>;
+    /// Finalizes the archive, flushing any buffered data.
+    fn finish(&mut self) -> Result<(), WriteError>;
+}
+
+impl ArchiveWriter for RoCrateZip {
+    fn start_entry(&mut self, name: &str, mode: u32) -> Result<(), WriteError> {
+        let options = if self.preserve_permissions {
+            self.options.unix_permissions(mode)
+        } else {
+            self.options
+        };
+        self.zip
+            .start_file(name, options)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), WriteError> {
+        self.zip.write_all(bytes).map_err(WriteError::IoError)
+    }
+
+    fn add_directory(&mut self, name: &str) -> Result<(), WriteError> {
+        self.zip
+            .add_directory(name, self.options)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))
+    }
+
+    fn finish(&mut self) -> Result<(), WriteError> {
+        self.zip
+            .finish()
+            .map(|_| ())
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))
+    }
+}
+
+/// Tar-based archive writer, optionally wrapped in gzip compression (see
+/// [`ArchiveFormat::TarGz`]). Tar entries must declare their size up front, so each
+/// entry's bytes are buffered here until the next `start_entry`/`add_directory`/`finish`
+/// call flushes the previous one.
+pub struct TarArchiveWriter<W: Write> {
+    builder: tar::Builder<W>,
+    pending: Option<(String, u32, Vec<u8>)>,
+}
+
+impl<W: Write> TarArchiveWriter<W> {
+    pub fn new(writer: W) -> Self {
+        TarArchiveWriter {
+            builder: tar::Builder::new(writer),
+            pending: None,
+        }
+    }
+
+    fn flush_pending(&mut self) -> Result<(), WriteError> {
+        if let Some((name, mode, data)) = self.pending.take() {
+            let mut header = Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(mode);
+            header.set_cksum();
+            self.builder
+                .append_data(&mut header, &name, data.as_slice())
+                .map_err(WriteError::IoError)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> ArchiveWriter for TarArchiveWriter<W> {
+    fn start_entry(&mut self, name: &str, mode: u32) -> Result<(), WriteError> {
+        self.flush_pending()?;
+        self.pending = Some((name.to_string(), mode, Vec::new()));
+        Ok(())
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), WriteError> {
+        match &mut self.pending {
+            Some((_, _, buffer)) => {
+                buffer.extend_from_slice(bytes);
+                Ok(())
+            }
+            None => Err(WriteError::ZipOperationError(
+                "write_all called before start_entry".to_string(),
+            )),
+        }
+    }
+
+    fn add_directory(&mut self, name: &str) -> Result<(), WriteError> {
+        self.flush_pending()?;
+        let mut header = Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        header.set_cksum();
+        self.builder
+            .append_data(&mut header, name, io::empty())
+            .map_err(WriteError::IoError)
+    }
+
+    fn finish(&mut self) -> Result<(), WriteError> {
+        self.flush_pending()?;
+        self.builder.finish().map_err(WriteError::IoError)
+    }
+}
+
+/// Constructs the archive writer selected by `format`, creating the backing file at
+/// `path_information.zip_file_name`.
+fn build_archive(
+    format: ArchiveFormat,
+    path_information: &RoCrateZipPaths,
+    compression: CompressionOptions,
+) -> Result<Box<dyn ArchiveWriter>, WriteError> {
+    match format {
+        ArchiveFormat::Zip => Ok(Box::new(build_zip(path_information, compression)?)),
+        ArchiveFormat::Tar => {
+            let file = File::create(&path_information.zip_file_name).map_err(WriteError::IoError)?;
+            Ok(Box::new(TarArchiveWriter::new(file)))
+        }
+        ArchiveFormat::TarGz => {
+            let file = File::create(&path_information.zip_file_name).map_err(WriteError::IoError)?;
+            let encoder = GzEncoder::new(file, Compression::default());
+            Ok(Box::new(TarArchiveWriter::new(encoder)))
+        }
+    }
+}
+
+/// Copies all bytes from `reader` into the entry most recently opened on `archive`,
+/// returning the number of bytes copied. Archive backends write through the
+/// [`ArchiveWriter`] trait rather than `io::Write` directly, so this stands in for
+/// `io::copy`.
+fn copy_into_archive<R: io::Read>(
+    reader: &mut R,
+    archive: &mut dyn ArchiveWriter,
+) -> Result<u64, WriteError> {
+    let mut buffer = [0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let bytes_read = reader.read(&mut buffer).map_err(WriteError::IoError)?;
+        if bytes_read == 0 {
+            break;
+        }
+        archive.write_all(&buffer[..bytes_read])?;
+        total += bytes_read as u64;
+    }
+    Ok(total)
+}
+
+/// Walks `root` once to cheaply count the files and total bytes that packaging is
+/// about to process, so `ZipProgress::on_start` can report a total up front.
+fn count_files_and_bytes(root: &Path) -> (usize, u64) {
+    let mut total_files = 0usize;
+    let mut total_bytes = 0u64;
+    for entry in WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+    {
+        total_files += 1;
+        total_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+    (total_files, total_bytes)
 }
 
 /// Serializes and writes an RO-Crate object to a JSON file.
@@ -60,6 +519,109 @@ pub fn write_crate(rocrate: &RoCrate, name: String) -> Result<(), WriteError> {
     Ok(())
 }
 
+/// File format used to serialize a crate's metadata document. JSON-LD is the RO-Crate
+/// specification's wire format and remains the default everywhere in this crate; YAML and
+/// TOML trade strict spec-compliance for a form that's comfortable to diff and hand-edit,
+/// for workflows that maintain `ro-crate-metadata.yaml`/`.toml` alongside the code it
+/// describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializationFormat {
+    Json,
+    Yaml,
+    Toml,
+    Ron,
+}
+
+impl SerializationFormat {
+    /// Guesses the format from a file name's extension, falling back to JSON for anything
+    /// unrecognised - including the conventional `ro-crate-metadata.json`.
+    pub fn from_path(name: &str) -> Self {
+        if name.ends_with(".yaml") || name.ends_with(".yml") {
+            SerializationFormat::Yaml
+        } else if name.ends_with(".toml") {
+            SerializationFormat::Toml
+        } else if name.ends_with(".ron") {
+            SerializationFormat::Ron
+        } else {
+            SerializationFormat::Json
+        }
+    }
+}
+
+/// Serializes and writes an RO-Crate in the given [`SerializationFormat`].
+///
+/// The in-memory graph is identical regardless of format - `@graph` maps onto a YAML
+/// sequence the same way it does a JSON array, and onto a TOML array of tables (`[[graph]]`)
+/// for the same reason. Use [`write_crate`] directly when JSON is all that's needed; this is
+/// the entry point for the YAML/TOML cases.
+///
+/// # Arguments
+/// * `rocrate` - A reference to the `RoCrate` object to serialize.
+/// * `name` - The name of the file to which the serialized crate should be written.
+/// * `format` - The serialization format to use.
+pub fn write_crate_as(
+    rocrate: &RoCrate,
+    name: String,
+    format: SerializationFormat,
+) -> Result<(), WriteError> {
+    match format {
+        SerializationFormat::Json => write_crate(rocrate, name),
+        SerializationFormat::Yaml => {
+            let yaml = serde_yaml::to_string(rocrate)
+                .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+            let mut file = File::create(name)?;
+            write!(file, "{}", yaml)?;
+            Ok(())
+        }
+        SerializationFormat::Toml => {
+            let toml = toml::to_string_pretty(rocrate)
+                .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+            let mut file = File::create(name)?;
+            write!(file, "{}", toml)?;
+            Ok(())
+        }
+        SerializationFormat::Ron => to_ron(rocrate, name),
+    }
+}
+
+/// Serializes an RO-Crate to RON (Rusty Object Notation) and writes it to `name`.
+///
+/// RON's trailing-comma tolerance, comments, and concise struct syntax make
+/// hand-authoring and diffing crate metadata far more pleasant than strict
+/// JSON-LD. This goes through the same `Serialize`/`Deserialize` impls as the
+/// JSON path (`CustomSerialize` for each `GraphVector` entity), so the
+/// `@id`/`@type`/dynamic-entity split is preserved - RON is just a different
+/// wire format for the same `RoCrate` structure.
+pub fn to_ron(rocrate: &RoCrate, name: String) -> Result<(), WriteError> {
+    let ron = ron::ser::to_string_pretty(rocrate, ron::ser::PrettyConfig::default())
+        .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+    let mut file = File::create(name)?;
+    write!(file, "{}", ron)?;
+    Ok(())
+}
+
+/// Reads a RON-encoded crate written by [`to_ron`] back into a `RoCrate`.
+pub fn from_ron(path: &Path) -> Result<RoCrate, WriteError> {
+    let contents = fs::read_to_string(path)?;
+    ron::de::from_str(&contents).map_err(|e| WriteError::ZipOperationError(e.to_string()))
+}
+
+/// Serializes and writes an RO-Crate object in its canonical JSON-LD form.
+///
+/// Unlike [`write_crate`], the bytes written here are deterministic: the same
+/// in-memory graph always produces the same file, which makes this the form to use
+/// before signing an `ro-crate-metadata.json` or content-addressing it by hash. See
+/// [`crate::ro_crate::canonical`] for the exact canonicalization rules.
+///
+/// # Arguments
+/// * `rocrate` - A reference to the `RoCrate` object to serialize.
+/// * `name` - The name of the file to which the canonical JSON-LD should be written.
+pub fn write_canonical_crate(rocrate: &RoCrate, name: String) -> Result<(), WriteError> {
+    let mut file = File::create(name)?;
+    crate::ro_crate::canonical::write_canonical(rocrate, &mut file)?;
+    Ok(())
+}
+
 /// Serializes an RO-Crate object and writes it directly to a zip file.
 ///
 /// This method allows for a modified RO-Crate to be efficiently serialized and saved into a zip archive
@@ -69,31 +631,23 @@ pub fn write_crate(rocrate: &RoCrate, name: String) -> Result<(), WriteError> {
 ///
 /// # Arguments
 /// * `rocrate` - A reference to the `RoCrate` object to serialize and save.
-/// * `name` - The name under which the serialized crate will be stored in the zip file.
-/// * `zip` - A mutable reference to the `ZipWriter` used for writing to the zip file.
-/// * `options` - ZipFile options to use when creating the new file in the zip archive.
+/// * `name` - The name under which the serialized crate will be stored in the archive.
+/// * `archive` - The archive writer to serialize into, independent of container format.
 ///
 /// # Returns
 /// A `Result<(), WriteError>` indicating the success or failure of the operation.
 fn write_crate_to_zip(
     rocrate: &RoCrate,
     name: String,
-    zip_data: &mut RoCrateZip,
+    archive: &mut dyn ArchiveWriter,
 ) -> Result<(), WriteError> {
     // Attempt to serialize the RoCrate object to a pretty JSON string
     let json_ld = serde_json::to_string_pretty(&rocrate)
         .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
 
-    // Start a new file in the zip archive with the given name and options
-    zip_data
-        .zip
-        .start_file(name, zip_data.options)
-        .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
-
-    zip_data
-        .zip
-        .write_all(json_ld.as_bytes())
-        .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+    // Start a new entry in the archive with the given name
+    archive.start_entry(&name, 0o644)?;
+    archive.write_all(json_ld.as_bytes())?;
 
     // If everything succeeded, return Ok(())
     Ok(())
@@ -111,6 +665,23 @@ fn write_crate_to_zip(
 /// # Arguments
 /// * `crate_path` - The path to the RO-Crate file within crate to zip.
 /// * `external` - A boolean flag indicating whether to apply special handling for external resources.
+/// * `create_entities` - If true, synthesises a minimal `File`/`Dataset` data entity for any
+///   walked file or directory the metadata doesn't already describe, and adds it to the root
+///   `Dataset`'s `hasPart` (see [`directory_walk`]).
+/// * `compression` - Compression method/level/permission handling for `ArchiveFormat::Zip`;
+///   ignored for the tar-based formats (see [`CompressionOptions`]).
+/// * `follow_symlinks` - If true, symlinked directories are walked into rather than archived
+///   as links, with a visited-path guard against symlink loops.
+/// * `checksums` - If enabled, fingerprints the `contentSize`/`sha256` (and optionally
+///   `sha512`) of each file packaged from the crate directory onto its data entity, and adds
+///   a `manifest-sha256.txt` entry to the archive (see [`ChecksumOptions`]); external and
+///   embedded remote files are not fingerprinted.
+/// * `embed_remote` - If true, downloads every `http(s)` `@id` in the crate and repacks it
+///   under `remote/` in the archive, rewriting the entity's `@id` to the packaged relative
+///   path (see [`embed_remote_has_part`]). Failures are skipped rather than aborting the zip.
+/// * `thread_count` - With the `rayon` feature enabled, the number of worker threads used to
+///   read and hash packaged files concurrently (see [`directory_walk`]); `0` lets `rayon`
+///   pick its own default (one per core). Ignored without that feature.
 ///
 /// # Returns
 /// A `Result<(), WriteError>` reflecting the success or failure of the operation.
@@ -131,9 +702,19 @@ pub fn zip_crate(
     validation_level: i8,
     flatten: bool,
     unique: bool,
+    match_list: Option<&MatchList>,
+    strict: bool,
+    progress: Option<&dyn ZipProgress>,
+    format: ArchiveFormat,
+    create_entities: bool,
+    compression: CompressionOptions,
+    follow_symlinks: bool,
+    checksums: ChecksumOptions,
+    embed_remote: bool,
+    thread_count: usize,
 ) -> Result<(), WriteError> {
-    // After prepping create the initial zip file
-    let mut zip_paths = construct_paths(crate_path)?;
+    // After prepping create the initial archive file
+    let mut zip_paths = construct_paths(crate_path, format)?;
     debug!("{:?}", &zip_paths);
 
     // Opens target crate ready for update
@@ -158,32 +739,69 @@ pub fn zip_crate(
             .ok_or_else(|| WriteError::ContextError("`@base` not found".to_string()))?;
 
         let stripped_id = format!(
-            "{}.zip",
+            "{}.{}",
             base_id
                 .strip_prefix("urn:uuid:")
                 .ok_or_else(|| WriteError::ContextError(
                     "`urn:uuid:` prefix not found".to_string()
-                ))?
+                ))?,
+            format.extension()
         );
         zip_paths.zip_file_name = zip_paths.root_path.join(stripped_id);
     }
     debug!("ZIP PATH NAME {:?}", zip_paths.zip_file_name);
 
-    let mut zip_data = build_zip(&zip_paths)?;
+    let mut archive = build_archive(format, &zip_paths, compression)?;
+
+    let (_, manifest_entries) = directory_walk(
+        &mut rocrate,
+        &zip_paths,
+        archive.as_mut(),
+        flatten,
+        match_list,
+        strict,
+        progress,
+        create_entities,
+        follow_symlinks,
+        checksums,
+        thread_count,
+    )?;
+
+    if checksums.enabled {
+        write_checksum_manifest(archive.as_mut(), &manifest_entries)?;
+    }
 
-    let _ = directory_walk(&mut rocrate, &zip_paths, &mut zip_data, flatten);
+    if embed_remote {
+        for outcome in embed_remote_has_part(&mut rocrate, archive.as_mut()) {
+            match outcome {
+                EmbedOutcome::Embedded { id, archive_path } => {
+                    debug!("Embedded remote resource {id} as {archive_path}");
+                }
+                EmbedOutcome::Skipped { id, reason } => {
+                    warn!("Skipped embedding remote resource {id}: {reason}");
+                }
+            }
+        }
+    }
 
     if external {
-        zip_data = zip_crate_external(&mut rocrate, zip_data, &zip_paths)?
+        zip_crate_external_filtered(
+            &mut rocrate,
+            archive.as_mut(),
+            &zip_paths,
+            match_list,
+            strict,
+            follow_symlinks,
+        )?;
     }
 
     let _ = write_crate_to_zip(
         &rocrate,
         "ro-crate-metadata.json".to_string(),
-        &mut zip_data,
+        archive.as_mut(),
     );
 
-    let _ = zip_data.zip.finish();
+    let _ = archive.finish();
 
     Ok(())
 }
@@ -194,7 +812,10 @@ pub struct RoCrateZipPaths {
     zip_file_name: PathBuf,
 }
 
-fn construct_paths(crate_path: &Path) -> Result<RoCrateZipPaths, WriteError> {
+fn construct_paths(
+    crate_path: &Path,
+    format: ArchiveFormat,
+) -> Result<RoCrateZipPaths, WriteError> {
     // TODO: add multiple options for walking/compression e.g follow symbolic links etc.
     let absolute_path = crate_path.canonicalize()?;
     let root_path = absolute_path
@@ -208,7 +829,8 @@ fn construct_paths(crate_path: &Path) -> Result<RoCrateZipPaths, WriteError> {
         .to_str()
         .ok_or(WriteError::FileNameConversionFailed)?;
 
-    let zip_file_name = root_path.join(format!("{}.zip", zip_file_base_name));
+    let zip_file_name =
+        root_path.join(format!("{}.{}", zip_file_base_name, format.extension()));
     Ok(RoCrateZipPaths {
         absolute_path,
         root_path,
@@ -216,22 +838,98 @@ fn construct_paths(crate_path: &Path) -> Result<RoCrateZipPaths, WriteError> {
     })
 }
 
-fn build_zip(path_information: &RoCrateZipPaths) -> Result<RoCrateZip, WriteError> {
+fn build_zip(
+    path_information: &RoCrateZipPaths,
+    compression: CompressionOptions,
+) -> Result<RoCrateZip, WriteError> {
     let file = File::create(&path_information.zip_file_name).map_err(WriteError::IoError)?;
     let zip = ZipWriter::new(file);
+    let preserve_permissions = compression.preserve_permissions;
+    let options = compression.to_zip_options()?;
 
-    // Can change this to deflated for standard compression
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .unix_permissions(0o755)
-        .large_file(true);
-
-    Ok(RoCrateZip { zip, options })
+    Ok(RoCrateZip {
+        zip,
+        options,
+        preserve_permissions,
+    })
 }
 
 pub struct RoCrateZip {
     zip: ZipWriter<File>,
     options: SimpleFileOptions,
+    preserve_permissions: bool,
+}
+
+/// One file discovered by [`directory_walk`]'s tree walk, queued for the read/hash pass.
+struct PendingFileEntry {
+    file_name: String,
+    abs_path: PathBuf,
+}
+
+/// Maximum number of [`PendingFileEntry`] values read and hashed into memory at once by
+/// [`directory_walk`]. Bounding this (rather than hashing the entire `pending` list up front)
+/// keeps a multi-gigabyte crate's packaged files from all being buffered simultaneously - only
+/// one batch's worth of file bytes is ever resident before being written to the archive and
+/// dropped.
+const HASH_QUEUE_DEPTH: usize = 64;
+
+/// Reads `path` fully into memory and folds its bytes through [`HashingReader`] to produce
+/// the same digests [`directory_walk`] used to compute while streaming the file straight into
+/// the archive. Buffering the whole file is what lets this run on a worker thread independent
+/// of the (single, non-`Send`) archive writer - see [`hash_pending_entries`].
+fn read_and_hash_file(path: &Path, checksums: ChecksumOptions) -> Result<(Vec<u8>, FileDigests), WriteError> {
+    let bytes = fs::read(path).map_err(WriteError::IoError)?;
+    let mut hashing_reader = HashingReader::new(io::Cursor::new(&bytes), checksums);
+    io::copy(&mut hashing_reader, &mut io::sink()).map_err(WriteError::IoError)?;
+    let digests = hashing_reader.finalize();
+    Ok((bytes, digests))
+}
+
+/// Reads and hashes every entry in `batch`, returning results in the same order they were
+/// given in. `batch` is expected to be one [`HASH_QUEUE_DEPTH`]-sized slice of a larger pending
+/// list rather than the whole thing, so memory use stays bounded regardless of crate size. With
+/// the `rayon` feature enabled, this runs across a thread pool sized to `thread_count` (`0`
+/// meaning rayon's own default of one thread per core); the entries themselves are `Sync` plain
+/// data, so nothing but the read+hash work is shared across threads. Without that feature,
+/// entries are read and hashed one at a time on this thread.
+fn hash_pending_entries(
+    batch: &[PendingFileEntry],
+    checksums: ChecksumOptions,
+    thread_count: usize,
+) -> Vec<Result<(Vec<u8>, FileDigests), WriteError>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let pool = match rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .build()
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                return batch
+                    .iter()
+                    .map(|_| Err(WriteError::ZipOperationError(e.to_string())))
+                    .collect()
+            }
+        };
+
+        pool.install(|| {
+            batch
+                .par_iter()
+                .map(|entry| read_and_hash_file(&entry.abs_path, checksums))
+                .collect()
+        })
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let _ = thread_count;
+        batch
+            .iter()
+            .map(|entry| read_and_hash_file(&entry.abs_path, checksums))
+            .collect()
+    }
 }
 
 /// Sole focus must be on present data
@@ -239,22 +937,140 @@ pub struct RoCrateZip {
 /// every file within it belongs to the crate. Whilst not everything is
 /// described in the ro-crate-metadata itself as per spec, it absolutely should
 /// get everything that is within the crate
+///
+/// When `create_entities` is set, every walked file or directory with no matching `@id`
+/// already in the graph gets a minimal `File`/`Dataset` entity synthesised for it (see
+/// [`create_file_entity`]/[`create_directory_entity`]) and linked into the root `Dataset`'s
+/// `hasPart`, run inline with the same walk so nothing gets stat'd twice.
+///
+/// When `follow_symlinks` is set, symlinked directories are walked into rather than
+/// archived as links. A visited-canonical-path set guards against a symlink loop inside
+/// the crate directory producing an infinite archive: once a directory's canonical path
+/// has been seen, its subtree is skipped on any later visit.
+///
+/// One file discovered by [`directory_walk`]'s tree walk, queued for the read/hash pass.
+struct PendingFileEntry {
+    file_name: String,
+    abs_path: PathBuf,
+}
+
+/// Maximum number of [`PendingFileEntry`] values read and hashed into memory at once by
+/// [`directory_walk`]. Bounding this (rather than hashing the entire `pending` list up front)
+/// keeps a multi-gigabyte crate's packaged files from all being buffered simultaneously - only
+/// one batch's worth of file bytes is ever resident before being written to the archive and
+/// dropped.
+const HASH_QUEUE_DEPTH: usize = 64;
+
+/// Reads `path` fully into memory and folds its bytes through [`HashingReader`] to produce
+/// the same digests [`directory_walk`] used to compute while streaming the file straight into
+/// the archive. Buffering the whole file is what lets this run on a worker thread independent
+/// of the (single, non-`Send`) archive writer - see [`hash_pending_entries`].
+fn read_and_hash_file(path: &Path, checksums: ChecksumOptions) -> Result<(Vec<u8>, FileDigests), WriteError> {
+    let bytes = fs::read(path).map_err(WriteError::IoError)?;
+    let mut hashing_reader = HashingReader::new(io::Cursor::new(&bytes), checksums);
+    io::copy(&mut hashing_reader, &mut io::sink()).map_err(WriteError::IoError)?;
+    let digests = hashing_reader.finalize();
+    Ok((bytes, digests))
+}
+
+/// Reads and hashes every entry in `batch`, returning results in the same order they were
+/// given in. `batch` is expected to be one [`HASH_QUEUE_DEPTH`]-sized slice of a larger pending
+/// list rather than the whole thing, so memory use stays bounded regardless of crate size. With
+/// the `rayon` feature enabled, this runs across a thread pool sized to `thread_count` (`0`
+/// meaning rayon's own default of one thread per core); the entries themselves are `Sync` plain
+/// data, so nothing but the read+hash work is shared across threads. Without that feature,
+/// entries are read and hashed one at a time on this thread.
+fn hash_pending_entries(
+    batch: &[PendingFileEntry],
+    checksums: ChecksumOptions,
+    thread_count: usize,
+) -> Vec<Result<(Vec<u8>, FileDigests), WriteError>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let pool = match rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .build()
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                return batch
+                    .iter()
+                    .map(|_| Err(WriteError::ZipOperationError(e.to_string())))
+                    .collect()
+            }
+        };
+
+        pool.install(|| {
+            batch
+                .par_iter()
+                .map(|entry| read_and_hash_file(&entry.abs_path, checksums))
+                .collect()
+        })
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let _ = thread_count;
+        batch
+            .iter()
+            .map(|entry| read_and_hash_file(&entry.abs_path, checksums))
+            .collect()
+    }
+}
+
+/// When `checksums.enabled` is set, each file's SHA-256 (and optionally SHA-512) digest is
+/// computed in the same pass that reads the file for hashing, via a [`HashingReader`]. The
+/// returned `Vec` pairs every packaged file's archive-relative path with its SHA-256 digest,
+/// for [`write_checksum_manifest`] to turn into a `manifest-sha256.txt` entry.
+///
+/// With the `rayon` feature enabled, reading and hashing a batch of files is farmed out across
+/// `thread_count` worker threads (`0` lets `rayon` pick its own default) - the I/O- and
+/// hash-bound part of packaging a crate. The actual write into the archive (which is where
+/// the `zip`/`tar` writer does its own compression) stays on this thread. Files are processed
+/// in [`HASH_QUEUE_DEPTH`]-sized batches: each batch is read and hashed, then written out and
+/// dropped before the next batch is read, so a multi-gigabyte payload
+/// never has more than a bounded slice of itself resident in memory at once. Entries are still
+/// walked and sorted by archive path up front, so the resulting archive's entry order - and
+/// thus its bytes, for a given compression method - is identical regardless of how many
+/// threads did the reading, in what order they finished, or how the batches fell.
 fn directory_walk(
     rocrate: &mut RoCrate,
     zip_paths: &RoCrateZipPaths,
-    zip_data: &mut RoCrateZip,
+    archive: &mut dyn ArchiveWriter,
     flatten: bool,
-) -> Result<Vec<PathBuf>, WriteError> {
+    match_list: Option<&MatchList>,
+    strict: bool,
+    progress: Option<&dyn ZipProgress>,
+    create_entities: bool,
+    follow_symlinks: bool,
+    checksums: ChecksumOptions,
+    thread_count: usize,
+) -> Result<(Vec<PathBuf>, Vec<(String, String)>), WriteError> {
     let mut data_vec: Vec<PathBuf> = Vec::new();
+    let mut manifest_entries: Vec<(String, String)> = Vec::new();
     let contained = get_noncontained_data_entites(rocrate, zip_paths, true)?;
+    let described_paths: std::collections::HashSet<PathBuf> =
+        contained.values().cloned().collect();
+    let mut visited_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+    let mut pending: Vec<PendingFileEntry> = Vec::new();
+
+    if let Some(progress) = progress {
+        let (total_files, total_bytes) = count_files_and_bytes(&zip_paths.root_path);
+        progress.on_start(total_files, total_bytes);
+    }
 
-    for entry in WalkDir::new(&zip_paths.root_path)
-        .min_depth(0)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-    // Consider only files, not directories
-    {
+    let mut walker = WalkDir::new(&zip_paths.root_path)
+        .min_depth(1)
+        .follow_links(follow_symlinks)
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
         let path = entry.path();
 
         if path == zip_paths.zip_file_name {
@@ -265,6 +1081,56 @@ fn directory_walk(
             continue;
         }
 
+        if entry.file_type().is_dir() {
+            if follow_symlinks {
+                if let Ok(canonical) = path.canonicalize() {
+                    if !visited_dirs.insert(canonical) {
+                        walker.skip_current_dir();
+                        continue;
+                    }
+                }
+            }
+
+            if create_entities {
+                let relative_path = path.strip_prefix(&zip_paths.root_path).unwrap_or(path);
+                if let Some(dir_name) = relative_path.to_str() {
+                    create_directory_entity(rocrate, dir_name);
+                    if let Some(GraphVector::RootDataEntity(root)) = rocrate
+                        .graph
+                        .iter_mut()
+                        .find(|graph_vector| matches!(graph_vector, GraphVector::RootDataEntity(_)))
+                    {
+                        root.add_entity_to_partof(dir_name.to_string());
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(match_list) = match_list {
+            let relative_path = path.strip_prefix(&zip_paths.root_path).unwrap_or(path);
+            let is_described = path
+                .canonicalize()
+                .map(|abs| described_paths.contains(&abs))
+                .unwrap_or(false);
+
+            if !match_list.is_included(relative_path) {
+                if is_described {
+                    if strict {
+                        return Err(WriteError::ExcludedDescribedEntity(
+                            path.display().to_string(),
+                        ));
+                    }
+                    warn!(
+                        "Force-including {:?}: excluded by match list but described in metadata",
+                        path
+                    );
+                } else {
+                    continue;
+                }
+            }
+        }
+
         let file_name: String = if flatten {
             path.file_name()
                 .ok_or(WriteError::FileNameNotFound)?
@@ -279,34 +1145,195 @@ fn directory_walk(
                 .to_string()
         };
 
-        let mut file = fs::File::open(path).map_err(WriteError::IoError)?;
-
-        zip_data
-            .zip
-            .start_file(&file_name, zip_data.options)
-            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
-
         // Once copy the absolute path and relative path needs to be checked
         let abs_path = path.canonicalize()?;
         if abs_path.is_file() {
             data_vec.push(abs_path.clone());
         };
 
-        let copy_result = io::copy(&mut file, &mut zip_data.zip).map_err(WriteError::IoError);
+        pending.push(PendingFileEntry {
+            file_name,
+            abs_path,
+        });
+    }
+
+    // Sorting here, before the (possibly out-of-order) concurrent read/hash pass, is what
+    // keeps the archive's entry order - and so its bytes - reproducible regardless of thread
+    // count or batch boundaries.
+    pending.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+
+    // Processed HASH_QUEUE_DEPTH entries at a time: each batch is hashed, written to the
+    // archive, and dropped before the next batch is read, instead of hashing the whole crate
+    // into memory up front.
+    for batch in pending.chunks(HASH_QUEUE_DEPTH) {
+        let hashed = hash_pending_entries(batch, checksums, thread_count);
+
+        for (entry, result) in batch.iter().zip(hashed) {
+            let (bytes, digests) = match result {
+                Ok(hashed) => hashed,
+                Err(e) => {
+                    error!("{e}");
+                    continue;
+                }
+            };
 
-        match copy_result {
-            Ok(_) => {
-                for (key, value) in &contained {
-                    if abs_path == value.clone() {
-                        rocrate.update_id_recursive(key, &file_name)
+            archive.start_entry(&entry.file_name, 0o644)?;
+            if let Err(e) = archive.write_all(&bytes) {
+                error!("{e}");
+                continue;
+            }
+            let bytes_written = bytes.len() as u64;
+
+            if let Some(progress) = progress {
+                progress.on_file(&entry.abs_path, bytes_written);
+            }
+            if let Some(sha256) = &digests.sha256 {
+                manifest_entries.push((entry.file_name.clone(), sha256.clone()));
+            }
+            let mut described = false;
+            for (key, value) in &contained {
+                if entry.abs_path == *value {
+                    described = true;
+                    rocrate.update_id_recursive(key, &entry.file_name);
+                    if let Some(GraphVector::DataEntity(data_entity)) =
+                        rocrate.get_entity_mutable(&entry.file_name)
+                    {
+                        data_entity.add_string_value(
+                            "contentSize".to_string(),
+                            bytes_written.to_string(),
+                        );
+                        if let Some(sha256) = &digests.sha256 {
+                            data_entity.add_string_value("sha256".to_string(), sha256.clone());
+                        }
+                        if let Some(sha512) = &digests.sha512 {
+                            data_entity.add_string_value("sha512".to_string(), sha512.clone());
+                        }
+                        if let Some(blake3) = &digests.blake3 {
+                            data_entity.add_string_value("blake3".to_string(), blake3.clone());
+                        }
                     }
                 }
             }
-            Err(e) => error!("{e}"),
+            if create_entities && !described {
+                create_file_entity(rocrate, &entry.file_name, &entry.abs_path, bytes_written);
+                if let Some(GraphVector::RootDataEntity(root)) = rocrate
+                    .graph
+                    .iter_mut()
+                    .find(|graph_vector| matches!(graph_vector, GraphVector::RootDataEntity(_)))
+                {
+                    root.add_entity_to_partof(entry.file_name.clone());
+                }
+            }
         }
     }
     debug!("0 | Rocrate: {:?}", rocrate);
-    Ok(data_vec)
+    if let Some(progress) = progress {
+        progress.on_finish();
+    }
+    Ok((data_vec, manifest_entries))
+}
+
+/// Writes a BagIt-style `manifest-sha256.txt` entry to `archive`, one `sha256  path` line
+/// per file [`directory_walk`] packaged with [`ChecksumOptions::enabled`] set, so the
+/// archive's payload can be verified independently of the `ro-crate-metadata.json` it
+/// carries.
+fn write_checksum_manifest(
+    archive: &mut dyn ArchiveWriter,
+    manifest_entries: &[(String, String)],
+) -> Result<(), WriteError> {
+    let mut manifest = String::new();
+    for (path, sha256) in manifest_entries {
+        manifest.push_str(&format!("{sha256}  {path}\n"));
+    }
+    archive.start_entry("manifest-sha256.txt", 0o644)?;
+    archive.write_all(manifest.as_bytes())?;
+    Ok(())
+}
+
+/// Best-effort `encodingFormat` guess for an undescribed file, from its extension alone.
+/// This is intentionally shallow - a handful of common research-data extensions - since
+/// sniffing file contents is out of scope for packaging.
+fn guess_encoding_format(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match extension.as_str() {
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "fa" | "fasta" | "fna" | "faa" => "text/x-fasta",
+        "gff" | "gff3" => "text/x-gff3",
+        "yaml" | "yml" => "application/yaml",
+        _ => return None,
+    };
+    Some(mime.to_string())
+}
+
+/// Synthesises a minimal `File` data entity for a walked file the metadata doesn't already
+/// describe, so that packaged crates don't silently carry undescribed files. Idempotent: does
+/// nothing if an entity with this id already exists (e.g. a previous `create_entities` run
+/// already created it).
+fn create_file_entity(rocrate: &mut RoCrate, entity_id: &str, path: &Path, content_size: u64) {
+    if rocrate.find_entity_index(entity_id).is_some() {
+        return;
+    }
+
+    let mut entity = DataEntity {
+        id: entity_id.to_string(),
+        type_: DataType::Term("File".to_string()),
+        dynamic_entity: None,
+    };
+    entity.add_string_value("contentSize".to_string(), content_size.to_string());
+    if let Some(encoding_format) = guess_encoding_format(path) {
+        entity.add_string_value("encodingFormat".to_string(), encoding_format);
+    }
+
+    rocrate.graph.push(GraphVector::DataEntity(entity));
+}
+
+/// Synthesises a minimal `Dataset` data entity for a walked directory the metadata doesn't
+/// already describe. Idempotent in the same way as [`create_file_entity`].
+fn create_directory_entity(rocrate: &mut RoCrate, entity_id: &str) {
+    if rocrate.find_entity_index(entity_id).is_some() {
+        return;
+    }
+
+    let entity = DataEntity {
+        id: entity_id.to_string(),
+        type_: DataType::Term("Dataset".to_string()),
+        dynamic_entity: None,
+    };
+
+    rocrate.graph.push(GraphVector::DataEntity(entity));
+}
+
+/// Verifies that a `DataEntity`'s recorded `sha256`, if any, still matches the bytes at `path`.
+///
+/// Entities that were never fingerprinted (e.g. packaged before this existed) are treated as
+/// trivially valid, so this is safe to run over any crate read from disk.
+pub fn verify_data_entity_fingerprint(data_entity: &DataEntity, path: &Path) -> Result<bool, WriteError> {
+    let expected_sha256 = match data_entity.dynamic_entity_immut() {
+        Some(fields) => match fields.get("sha256") {
+            Some(DynamicEntity::EntityString(value)) => value.clone(),
+            _ => return Ok(true),
+        },
+        None => return Ok(true),
+    };
+
+    let contents = fs::read(path).map_err(WriteError::IoError)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&contents);
+    let actual_sha256 = format!("{:x}", hasher.finalize());
+
+    Ok(actual_sha256 == expected_sha256)
 }
 
 /// Packages an RO-Crate and its external files into a zip archive, updating IDs as necessary.
@@ -319,17 +1346,470 @@ fn directory_walk(
 /// # Arguments
 /// * `rocrate` - A mutable reference to the `RoCrate` object being packaged.
 /// * `crate_path` - The filesystem path to the directory containing the RO-Crate's metadata and data entities.
-/// * `zip` - A `ZipWriter<File>` for writing to the zip archive.
-/// * `options` - `SimpleFileOptions` determining how files are added to the archive (e.g., compression level).
+/// * `archive` - The archive writer files are packaged into, independent of container format.
 ///
 /// # Returns
-/// Returns a `Result` containing the updated `ZipWriter<File>` on success, or a `WriteError` on failure,
-/// encapsulating any errors that occurred during the operation.
+/// Returns `Ok(())` on success, or a `WriteError` on failure, encapsulating any errors
+/// that occurred during the operation.
 pub fn zip_crate_external(
     rocrate: &mut RoCrate,
-    mut zip_data: RoCrateZip,
+    archive: &mut dyn ArchiveWriter,
     crate_path: &RoCrateZipPaths,
-) -> Result<RoCrateZip, WriteError> {
+) -> Result<(), WriteError> {
+    zip_crate_external_filtered(rocrate, archive, crate_path, None, false, false)
+}
+
+/// The crate-relative path prefix [`zip_crate_external_filtered`] copies external data
+/// entities under when building an archive.
+const EXTERNAL_ENTRY_PREFIX: &str = "external/";
+
+/// A single entry in an archive (see [`zip_crate`]) and its uncompressed size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntryInfo {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Determines an archive's format from its file extension, since archives built by
+/// [`zip_crate`] are always named `<base>.<extension>` (see [`ArchiveFormat::extension`]).
+fn archive_format_from_path(archive: &Path) -> Result<ArchiveFormat, WriteError> {
+    let name = archive
+        .to_str()
+        .ok_or(WriteError::FileNameConversionFailed)?;
+
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Ok(ArchiveFormat::TarGz)
+    } else if name.ends_with(".tar") {
+        Ok(ArchiveFormat::Tar)
+    } else if name.ends_with(".zip") {
+        Ok(ArchiveFormat::Zip)
+    } else {
+        Err(WriteError::ZipOperationError(format!(
+            "unrecognised archive extension: {name}"
+        )))
+    }
+}
+
+/// Enumerates every entry in an archive and its size, without extracting any file
+/// contents - useful for callers that just want an index of what a packaged crate
+/// contains.
+pub fn list_archive_entries(archive: &Path) -> Result<Vec<ArchiveEntryInfo>, WriteError> {
+    match archive_format_from_path(archive)? {
+        ArchiveFormat::Zip => list_zip_entries(archive),
+        ArchiveFormat::Tar => list_tar_entries(archive, false),
+        ArchiveFormat::TarGz => list_tar_entries(archive, true),
+    }
+}
+
+fn list_zip_entries(archive_path: &Path) -> Result<Vec<ArchiveEntryInfo>, WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+    let mut zip_archive =
+        zip::ZipArchive::new(file).map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+
+    let mut entries = Vec::with_capacity(zip_archive.len());
+    for i in 0..zip_archive.len() {
+        let entry = zip_archive
+            .by_index(i)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+        entries.push(ArchiveEntryInfo {
+            name: entry.name().to_string(),
+            size: entry.size(),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn list_tar_entries(archive_path: &Path, gzip: bool) -> Result<Vec<ArchiveEntryInfo>, WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+
+    if gzip {
+        collect_tar_entries(tar::Archive::new(GzDecoder::new(file)))
+    } else {
+        collect_tar_entries(tar::Archive::new(file))
+    }
+}
+
+fn collect_tar_entries<R: io::Read>(
+    mut archive: tar::Archive<R>,
+) -> Result<Vec<ArchiveEntryInfo>, WriteError> {
+    let mut entries = Vec::new();
+    for entry in archive.entries().map_err(WriteError::IoError)? {
+        let entry = entry.map_err(WriteError::IoError)?;
+        let name = entry
+            .path()
+            .map_err(WriteError::IoError)?
+            .to_string_lossy()
+            .to_string();
+        let size = entry.header().size().map_err(WriteError::IoError)?;
+        entries.push(ArchiveEntryInfo { name, size });
+    }
+    Ok(entries)
+}
+
+/// A manifest entry whose recorded digest no longer matches the packaged bytes, as
+/// reported by [`verify_archive_checksums`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub path: String,
+    pub expected_sha256: String,
+    pub actual_sha256: String,
+}
+
+/// Re-hashes every entry listed in an archive's `manifest-sha256.txt` (written by
+/// [`zip_crate`] when [`ChecksumOptions::enabled`] is set) directly from the archive's
+/// bytes, without extracting to disk, and reports any path whose digest no longer matches.
+///
+/// Returns `Ok(None)` if the archive carries no `manifest-sha256.txt` entry - packaged
+/// without checksums, so there's nothing to verify - rather than an empty mismatch list,
+/// so callers can distinguish "nothing to verify" from "everything verified clean".
+pub fn verify_archive_checksums(
+    archive: &Path,
+) -> Result<Option<Vec<ChecksumMismatch>>, WriteError> {
+    match archive_format_from_path(archive)? {
+        ArchiveFormat::Zip => verify_zip_checksums(archive),
+        ArchiveFormat::Tar => verify_tar_checksums(archive, false),
+        ArchiveFormat::TarGz => verify_tar_checksums(archive, true),
+    }
+}
+
+fn hash_manifest_against(
+    manifest_text: &str,
+    mut read_entry: impl FnMut(&str) -> Option<Vec<u8>>,
+) -> Vec<ChecksumMismatch> {
+    let mut mismatches = Vec::new();
+    for line in manifest_text.lines() {
+        let Some((expected_sha256, path)) = line.split_once("  ") else {
+            continue;
+        };
+        let Some(contents) = read_entry(path) else {
+            continue;
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(&contents);
+        let actual_sha256 = format!("{:x}", hasher.finalize());
+        if actual_sha256 != expected_sha256 {
+            mismatches.push(ChecksumMismatch {
+                path: path.to_string(),
+                expected_sha256: expected_sha256.to_string(),
+                actual_sha256,
+            });
+        }
+    }
+    mismatches
+}
+
+fn verify_zip_checksums(archive_path: &Path) -> Result<Option<Vec<ChecksumMismatch>>, WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+    let mut zip_archive =
+        zip::ZipArchive::new(file).map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+
+    let manifest_text = {
+        let mut entry = match zip_archive.by_name("manifest-sha256.txt") {
+            Ok(entry) => entry,
+            Err(_) => return Ok(None),
+        };
+        let mut text = String::new();
+        entry.read_to_string(&mut text).map_err(WriteError::IoError)?;
+        text
+    };
+
+    let mismatches = hash_manifest_against(&manifest_text, |path| {
+        let mut entry = zip_archive.by_name(path).ok()?;
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).ok()?;
+        Some(contents)
+    });
+
+    Ok(Some(mismatches))
+}
+
+fn verify_tar_checksums(
+    archive_path: &Path,
+    gzip: bool,
+) -> Result<Option<Vec<ChecksumMismatch>>, WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+
+    let mut entries_by_path: HashMap<String, Vec<u8>> = HashMap::new();
+    if gzip {
+        collect_tar_bytes(tar::Archive::new(GzDecoder::new(file)), &mut entries_by_path)?;
+    } else {
+        collect_tar_bytes(tar::Archive::new(file), &mut entries_by_path)?;
+    }
+
+    let Some(manifest_bytes) = entries_by_path.get("manifest-sha256.txt") else {
+        return Ok(None);
+    };
+    let manifest_text = String::from_utf8_lossy(manifest_bytes).to_string();
+
+    let mismatches =
+        hash_manifest_against(&manifest_text, |path| entries_by_path.get(path).cloned());
+
+    Ok(Some(mismatches))
+}
+
+fn collect_tar_bytes<R: io::Read>(
+    mut archive: tar::Archive<R>,
+    out: &mut HashMap<String, Vec<u8>>,
+) -> Result<(), WriteError> {
+    for entry in archive.entries().map_err(WriteError::IoError)? {
+        let mut entry = entry.map_err(WriteError::IoError)?;
+        let path = entry
+            .path()
+            .map_err(WriteError::IoError)?
+            .to_string_lossy()
+            .to_string();
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).map_err(WriteError::IoError)?;
+        out.insert(path, contents);
+    }
+    Ok(())
+}
+
+/// Extracts every entry of an archive built by [`zip_crate`] to `dest`, recreating its
+/// directory structure, the inverse of packaging.
+///
+/// When `restore_external` is set, any data entity whose `@id` was rewritten under the
+/// `external/` prefix by [`zip_crate_external_filtered`] is moved out of the extracted
+/// `external/` folder into `dest` directly and has its `@id` rewritten back to a plain
+/// relative path, so the extracted crate validates like any other directory crate.
+///
+/// Returns the path to the extracted `ro-crate-metadata.json`.
+pub fn unzip_crate(
+    archive: &Path,
+    dest: &Path,
+    restore_external: bool,
+) -> Result<PathBuf, WriteError> {
+    fs::create_dir_all(dest).map_err(WriteError::IoError)?;
+
+    match archive_format_from_path(archive)? {
+        ArchiveFormat::Zip => extract_zip(archive, dest)?,
+        ArchiveFormat::Tar => extract_tar(archive, dest, false)?,
+        ArchiveFormat::TarGz => extract_tar(archive, dest, true)?,
+    }
+
+    let metadata_path = dest.join("ro-crate-metadata.json");
+
+    if restore_external {
+        let mut rocrate = read_crate(&metadata_path, 0)?;
+        restore_external_entities(&mut rocrate, dest)?;
+        write_crate(
+            &rocrate,
+            metadata_path.canonicalize()?.to_string_lossy().to_string(),
+        )?;
+    }
+
+    Ok(metadata_path)
+}
+
+fn extract_zip(archive_path: &Path, dest: &Path) -> Result<(), WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+    let mut zip_archive =
+        zip::ZipArchive::new(file).map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+
+    for i in 0..zip_archive.len() {
+        let mut entry = zip_archive
+            .by_index(i)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+        let out_path = match entry.enclosed_name() {
+            Some(path) => dest.join(path),
+            None => continue,
+        };
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path).map_err(WriteError::IoError)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent).map_err(WriteError::IoError)?;
+            }
+            let mut out_file = File::create(&out_path).map_err(WriteError::IoError)?;
+            io::copy(&mut entry, &mut out_file).map_err(WriteError::IoError)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_tar(archive_path: &Path, dest: &Path, gzip: bool) -> Result<(), WriteError> {
+    let file = File::open(archive_path).map_err(WriteError::IoError)?;
+
+    if gzip {
+        tar::Archive::new(GzDecoder::new(file))
+            .unpack(dest)
+            .map_err(WriteError::IoError)
+    } else {
+        tar::Archive::new(file).unpack(dest).map_err(WriteError::IoError)
+    }
+}
+
+/// Extracts every `@id` an `EntityValue` resolves to, whether it's a single reference
+/// or an array of references - the same shape `hasPart` and similar linking properties
+/// take across the four `GraphVector` variants.
+fn referenced_ids(value: &EntityValue) -> Vec<String> {
+    match value {
+        EntityValue::EntityId(Id::Id(id_value)) => vec![id_value.id.clone()],
+        EntityValue::EntityId(Id::IdArray(id_values)) => {
+            id_values.iter().map(|id_value| id_value.id.clone()).collect()
+        }
+        // Crates written before id-valued `hasPart` entries were always stored as
+        // `EntityValue::EntityId` may still have a comma-joined string left over from that
+        // older representation; fall back to splitting it so this check doesn't silently
+        // ignore every entity linked that way.
+        EntityValue::EntityString(joined) => joined
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Opens a `.zip`-packaged RO-Crate without extracting it to disk: locates the first
+/// archive entry whose name ends in `ro-crate-metadata.json` (at the root, where
+/// `zip_crate` writes it, or under a folder prefix, which is then applied to every
+/// entity lookup), deserializes it, and resolves every described data entity `@id` to the
+/// archive entry that holds its bytes.
+///
+/// Returns the parsed [`RoCrate`] alongside a map from entity `@id` to the zip entry
+/// name it was found under. A `hasPart` reference with no matching entry anywhere in
+/// the archive is a hard error rather than a silently incomplete map, since it means
+/// the crate and the archive have drifted apart.
+pub fn read_crate_from_zip(archive: &Path) -> Result<(RoCrate, HashMap<String, String>), WriteError> {
+    let file = File::open(archive).map_err(WriteError::IoError)?;
+    let mut zip_archive =
+        zip::ZipArchive::new(file).map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+
+    let entry_names: Vec<String> = (0..zip_archive.len())
+        .filter_map(|i| zip_archive.name_for_index(i).map(str::to_string))
+        .collect();
+
+    let metadata_entry_name = entry_names
+        .iter()
+        .find(|name| name.ends_with("ro-crate-metadata.json"))
+        .cloned()
+        .ok_or_else(|| WriteError::NoSuchZipEntry("ro-crate-metadata.json".to_string()))?;
+
+    let prefix = metadata_entry_name
+        .strip_suffix("ro-crate-metadata.json")
+        .unwrap_or("")
+        .to_string();
+
+    let metadata_contents = {
+        let mut entry = zip_archive
+            .by_name(&metadata_entry_name)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .map_err(WriteError::IoError)?;
+        contents
+    };
+
+    let rocrate: RoCrate =
+        serde_json::from_str(&metadata_contents).map_err(WriteError::Serialization)?;
+
+    let mut payload_paths: HashMap<String, String> = HashMap::new();
+    for id in rocrate.get_all_ids() {
+        let zip_entry_name = format!("{prefix}{id}");
+        if entry_names.contains(&zip_entry_name) {
+            payload_paths.insert(id.clone(), zip_entry_name);
+        }
+    }
+
+    for (_, value) in rocrate.get_all_property_values("hasPart") {
+        for referenced_id in referenced_ids(&value) {
+            let zip_entry_name = format!("{prefix}{referenced_id}");
+            let as_dir = format!("{zip_entry_name}/");
+            if !entry_names.contains(&zip_entry_name) && !entry_names.contains(&as_dir) {
+                return Err(WriteError::HasPartEntryMissing(referenced_id));
+            }
+        }
+    }
+
+    Ok((rocrate, payload_paths))
+}
+
+/// Serializes `rocrate` plus a set of referenced local files into a conformant `.zip`:
+/// `ro-crate-metadata.json` at the archive root, and every `(entity_id, source_path)`
+/// pair written under an entry named after the entity's own `@id`, mirroring the layout
+/// [`read_crate_from_zip`] expects to read back.
+pub fn write_crate_to_zip_with_files(
+    rocrate: &RoCrate,
+    archive: &Path,
+    files: &HashMap<String, PathBuf>,
+) -> Result<(), WriteError> {
+    let file = File::create(archive).map_err(WriteError::IoError)?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let metadata_json =
+        serde_json::to_string_pretty(rocrate).map_err(WriteError::Serialization)?;
+    zip.start_file("ro-crate-metadata.json", options)
+        .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+    zip.write_all(metadata_json.as_bytes())
+        .map_err(WriteError::IoError)?;
+
+    for (entity_id, source_path) in files {
+        let mut source_file = File::open(source_path).map_err(WriteError::IoError)?;
+        zip.start_file(entity_id.as_str(), options)
+            .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+        io::copy(&mut source_file, &mut zip).map_err(WriteError::IoError)?;
+    }
+
+    zip.finish()
+        .map(|_| ())
+        .map_err(|e| WriteError::ZipOperationError(e.to_string()))
+}
+
+/// Moves every data entity currently `@id`-ed under `external/` back to `dest` directly,
+/// rewriting its `@id` to match, so the extracted crate no longer depends on the
+/// `external/` packaging convention.
+fn restore_external_entities(rocrate: &mut RoCrate, dest: &Path) -> Result<(), WriteError> {
+    let external_ids: Vec<String> = rocrate
+        .get_all_ids()
+        .into_iter()
+        .filter(|id| id.starts_with(EXTERNAL_ENTRY_PREFIX))
+        .cloned()
+        .collect();
+
+    for old_id in external_ids {
+        let new_id = old_id
+            .strip_prefix(EXTERNAL_ENTRY_PREFIX)
+            .unwrap_or(&old_id)
+            .to_string();
+
+        let old_path = dest.join(&old_id);
+        let new_path = dest.join(&new_id);
+        if old_path.exists() {
+            if let Some(parent) = new_path.parent() {
+                fs::create_dir_all(parent).map_err(WriteError::IoError)?;
+            }
+            fs::rename(&old_path, &new_path).map_err(WriteError::IoError)?;
+        }
+
+        rocrate.update_id_recursive(&old_id, &new_id);
+    }
+
+    let external_dir = dest.join("external");
+    if external_dir.is_dir() {
+        let _ = fs::remove_dir(&external_dir);
+    }
+
+    Ok(())
+}
+
+/// As [`zip_crate_external`], but honouring `match_list` the same way `directory_walk`
+/// does: a described external entity excluded by the match list is force-included with
+/// a warning, unless `strict` is set, in which case it is a hard error.
+pub fn zip_crate_external_filtered(
+    rocrate: &mut RoCrate,
+    archive: &mut dyn ArchiveWriter,
+    crate_path: &RoCrateZipPaths,
+    match_list: Option<&MatchList>,
+    strict: bool,
+    follow_symlinks: bool,
+) -> Result<(), WriteError> {
     // This parses all the IDs and generates a list of paths that are not
     // contained within the crate itself.
     let noncontained = get_noncontained_data_entites(rocrate, crate_path, false)?;
@@ -346,21 +1826,29 @@ pub fn zip_crate_external(
                 .ok_or(WriteError::FileNameConversionFailed)?;
             let zip_entry_name = format!("external/{}", file_name);
 
+            if let Some(match_list) = match_list {
+                if !match_list.is_included(Path::new(&zip_entry_name)) {
+                    if strict {
+                        return Err(WriteError::ExcludedDescribedEntity(zip_entry_name));
+                    }
+                    warn!(
+                        "Force-including {:?}: excluded by match list but described in metadata",
+                        zip_entry_name
+                    );
+                }
+            }
+
             if path.is_dir() {
                 // It's a directory -> recursively add all of its contents
-                add_directory_recursively(&path, &zip_entry_name, &mut zip_data)?;
+                add_directory_recursively(&path, &zip_entry_name, archive, follow_symlinks)?;
 
                 rocrate.update_id_recursive(&id, &zip_entry_name);
             } else if path.is_file() {
                 let mut file = fs::File::open(&path).map_err(WriteError::IoError)?;
 
-                zip_data
-                    .zip
-                    .start_file(&zip_entry_name, zip_data.options)
-                    .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+                archive.start_entry(&zip_entry_name, 0o644)?;
 
-                let copy_result =
-                    io::copy(&mut file, &mut zip_data.zip).map_err(WriteError::IoError);
+                let copy_result = copy_into_archive(&mut file, archive);
                 match copy_result {
                     Ok(_) => {
                         rocrate.update_id_recursive(&id, &zip_entry_name);
@@ -373,7 +1861,7 @@ pub fn zip_crate_external(
         }
     }
 
-    Ok(zip_data)
+    Ok(())
 }
 
 /// Gets all the described data entities of a crate and filters for
@@ -542,6 +2030,152 @@ pub fn is_not_url(path: &str) -> bool {
     Url::parse(path).is_err()
 }
 
+/// Classifies an identifier by URI scheme using real parsing rather than [`is_not_url`]'s
+/// scheme-prefix guesswork, since different kinds of URI need different handling when
+/// preparing a crate for packaging: `http(s)` resources can be fetched, `file://` resources map
+/// onto a local filesystem path, `data:` resources already carry their bytes inline, and
+/// anything else is an opaque reference left for the caller to resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriKind {
+    Http(String),
+    File(PathBuf),
+    Data {
+        mime: Option<String>,
+        bytes: Vec<u8>,
+    },
+    Opaque(String),
+}
+
+/// Classifies `id` by URI scheme (see [`UriKind`]). `data:` URIs are decoded eagerly, since
+/// their payload is already inline; anything that fails to parse as a URL at all, or whose
+/// scheme isn't one of the above, is treated as opaque.
+pub fn classify_uri(id: &str) -> UriKind {
+    let Ok(url) = Url::parse(id) else {
+        return UriKind::Opaque(id.to_string());
+    };
+
+    match url.scheme() {
+        "http" | "https" => UriKind::Http(id.to_string()),
+        "file" => match url.to_file_path() {
+            Ok(path) => UriKind::File(path),
+            Err(()) => UriKind::Opaque(id.to_string()),
+        },
+        "data" => match decode_data_uri(&url) {
+            Some((mime, bytes)) => UriKind::Data { mime, bytes },
+            None => UriKind::Opaque(id.to_string()),
+        },
+        _ => UriKind::Opaque(id.to_string()),
+    }
+}
+
+/// Decodes a `data:[<mediatype>];base64,<data>` URI into its (optional) media type and raw
+/// bytes. Only the base64-encoded form is supported; a `data:` URI carrying plain, non-base64
+/// text is reported as opaque rather than guessed at.
+fn decode_data_uri(url: &Url) -> Option<(Option<String>, Vec<u8>)> {
+    let payload = url.path();
+    let (header, data) = payload.split_once(',')?;
+    let mime = header.strip_suffix(";base64")?;
+    let mime = (!mime.is_empty()).then(|| mime.to_string());
+    let bytes = STANDARD.decode(data).ok()?;
+    Some((mime, bytes))
+}
+
+/// Outcome of attempting to embed one remote resource into the archive (see
+/// [`embed_remote_has_part`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbedOutcome {
+    Embedded { id: String, archive_path: String },
+    Skipped { id: String, reason: String },
+}
+
+/// Downloads every `http(s)` `@id` referenced anywhere in the crate and repacks it into the
+/// archive under `remote/<name>`, rewriting the entity's `@id` (and any `hasPart` references to
+/// it) to the packaged relative path, so the resulting crate is self-contained. Network errors
+/// and non-success responses are collected as [`EmbedOutcome::Skipped`] rather than aborting the
+/// whole pass, leaving the original remote reference in place.
+fn embed_remote_has_part(rocrate: &mut RoCrate, archive: &mut dyn ArchiveWriter) -> Vec<EmbedOutcome> {
+    let remote_ids: Vec<String> = rocrate
+        .get_all_ids()
+        .into_iter()
+        .filter(|id| matches!(classify_uri(id), UriKind::Http(_)))
+        .cloned()
+        .collect();
+
+    let mut outcomes = Vec::new();
+    for id in remote_ids {
+        match fetch_remote_resource(&id) {
+            Ok((bytes, content_type)) => {
+                let archive_path =
+                    format!("remote/{}", remote_file_name(&id, content_type.as_deref()));
+                let written: Result<(), WriteError> = (|| {
+                    archive.start_entry(&archive_path, 0o644)?;
+                    archive.write_all(&bytes)
+                })();
+                match written {
+                    Ok(()) => {
+                        rocrate.update_id_recursive(&id, &archive_path);
+                        rocrate.add_data_to_partof_root(&archive_path);
+                        outcomes.push(EmbedOutcome::Embedded { id, archive_path });
+                    }
+                    Err(e) => outcomes.push(EmbedOutcome::Skipped {
+                        id,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            Err(reason) => outcomes.push(EmbedOutcome::Skipped { id, reason }),
+        }
+    }
+    outcomes
+}
+
+/// Fetches `url` (following redirects, per `reqwest`'s default client behaviour) and returns its
+/// bytes alongside the response's `Content-Type` header, if present. Errors are flattened to a
+/// message string rather than threaded through [`WriteError`], since the caller treats any
+/// failure here as a per-resource skip rather than a fatal error.
+fn fetch_remote_resource(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
+    let response = reqwest::blocking::get(url).map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let bytes = response.bytes().map_err(|e| e.to_string())?.to_vec();
+    Ok((bytes, content_type))
+}
+
+/// Derives a packaged file name for a remote resource: the URL's last path segment if it looks
+/// like a file name, otherwise a generic name with an extension guessed from the content type.
+fn remote_file_name(url: &str, content_type: Option<&str>) -> String {
+    if let Ok(parsed) = Url::parse(url) {
+        if let Some(segment) = parsed.path_segments().and_then(|mut segments| segments.next_back()) {
+            if !segment.is_empty() {
+                return segment.to_string();
+            }
+        }
+    }
+    let extension = content_type.and_then(extension_for_mime).unwrap_or("bin");
+    format!("resource.{extension}")
+}
+
+/// Maps a handful of common MIME types to a file extension, for naming embedded resources whose
+/// URL gives no usable file name.
+fn extension_for_mime(content_type: &str) -> Option<&'static str> {
+    match content_type.split(';').next().unwrap_or("").trim() {
+        "text/plain" => Some("txt"),
+        "text/csv" => Some("csv"),
+        "application/json" => Some("json"),
+        "application/pdf" => Some("pdf"),
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "application/zip" => Some("zip"),
+        _ => None,
+    }
+}
+
 /// Checks if a given file path lies outside of a specified base folder.
 ///
 /// This function is critical in identifying external resources that need special handling when
@@ -567,13 +2201,28 @@ fn is_outside_base_folder(base_folder: &Path, file_path: &Path) -> bool {
 }
 
 /// Recursively adds an entire directory (and subdirectories) into the ZIP under `zip_prefix/…`.
+///
+/// When `follow_symlinks` is set, symlinked directories are walked into rather than
+/// archived as links, with the same visited-canonical-path cycle guard as
+/// [`directory_walk`].
 fn add_directory_recursively(
     base_dir: &Path,
     zip_prefix: &str,
-    zip_data: &mut RoCrateZip,
+    archive: &mut dyn ArchiveWriter,
+    follow_symlinks: bool,
 ) -> Result<(), WriteError> {
+    let mut visited_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
     // WalkDir will yield subdirectories and files.
-    for entry in WalkDir::new(base_dir).into_iter().filter_map(|e| e.ok()) {
+    let mut walker = WalkDir::new(base_dir)
+        .follow_links(follow_symlinks)
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
         let p = entry.path();
         debug!("p = {:?}", p);
 
@@ -588,19 +2237,21 @@ fn add_directory_recursively(
         let zip_entry_name = format!("{}/{}", zip_prefix, relative_subpath.display());
         debug!("zp entry name: {:?}", zip_entry_name);
         if p.is_dir() {
+            if follow_symlinks {
+                if let Ok(canonical) = p.canonicalize() {
+                    if !visited_dirs.insert(canonical) {
+                        walker.skip_current_dir();
+                        continue;
+                    }
+                }
+            }
             // Optional: add an explicit directory entry in the archive:
-            zip_data
-                .zip
-                .add_directory(zip_entry_name, zip_data.options)
-                .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
+            archive.add_directory(&zip_entry_name)?;
         } else if p.is_file() {
             let mut file = fs::File::open(p).map_err(WriteError::IoError)?;
             debug!("FILE: {:?}", file);
-            zip_data
-                .zip
-                .start_file(&zip_entry_name, zip_data.options)
-                .map_err(|e| WriteError::ZipOperationError(e.to_string()))?;
-            io::copy(&mut file, &mut zip_data.zip).map_err(WriteError::IoError)?;
+            archive.start_entry(&zip_entry_name, 0o644)?;
+            copy_into_archive(&mut file, archive)?;
         }
     }
     Ok(())
@@ -740,7 +2391,7 @@ mod write_crate_tests {
             .join("test_experiment/_ro-crate-metadata-minimal.json");
         let _file = std::fs::File::create_new(&file_path).unwrap();
 
-        let paths = construct_paths(&file_path).unwrap();
+        let paths = construct_paths(&file_path, ArchiveFormat::Zip).unwrap();
 
         assert_eq!(paths.absolute_path, file_path.canonicalize().unwrap());
         assert_eq!(paths.root_path, dir_path.canonicalize().unwrap());
@@ -780,12 +2431,25 @@ mod write_crate_tests {
             zip: ZipWriter::new(File::create(&zip_paths.zip_file_name).unwrap()),
             options: SimpleFileOptions::default()
                 .compression_method(zip::CompressionMethod::Deflated),
+            preserve_permissions: true,
         };
 
         let mut rocrate = read_crate(&file_path, 0).unwrap();
 
-        let mut directory_contents =
-            directory_walk(&mut rocrate, &zip_paths, &mut zip_data, false).unwrap();
+        let (mut directory_contents, _manifest_entries) = directory_walk(
+            &mut rocrate,
+            &zip_paths,
+            &mut zip_data,
+            false,
+            None,
+            false,
+            None,
+            false,
+            false,
+            ChecksumOptions::default(),
+            0,
+        )
+        .unwrap();
 
         let mut test_vec: Vec<PathBuf> = vec![
             dir_path.join(PathBuf::from("data.csv")),
@@ -929,13 +2593,14 @@ mod write_crate_tests {
             zip_file_name: dir_path.join(PathBuf::from("test_experiment.zip")),
         };
 
-        let zip_data = RoCrateZip {
+        let mut zip_data = RoCrateZip {
             zip: ZipWriter::new(File::create(&zip_paths.zip_file_name).unwrap()),
             options: SimpleFileOptions::default()
                 .compression_method(zip::CompressionMethod::Deflated),
+            preserve_permissions: true,
         };
 
-        assert!(zip_crate_external(&mut rocrate, zip_data, &zip_paths).is_ok());
+        assert!(zip_crate_external(&mut rocrate, &mut zip_data, &zip_paths).is_ok());
     }
 
     #[test]
@@ -968,7 +2633,7 @@ mod write_crate_tests {
         let base = path.path().file_name().unwrap().to_string_lossy();
         let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
 
-        let zipped = zip_crate(&ro_path, false, 0, false, false);
+        let zipped = zip_crate(&ro_path, false, 0, false, false, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
         println!("{:?}", zipped);
         assert!(parse_zip(&zip_path, 0).is_ok());
     }
@@ -980,7 +2645,7 @@ mod write_crate_tests {
         let base = path.path().file_name().unwrap().to_string_lossy();
         let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
 
-        let zipped = zip_crate(&ro_path, true, 0, false, false);
+        let zipped = zip_crate(&ro_path, true, 0, false, false, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
         println!("{:?}", zipped);
         assert!(parse_zip(&zip_path, 0).is_ok());
     }
@@ -990,7 +2655,7 @@ mod write_crate_tests {
         let path = create_tempcrate(&minimal_test_experiment_rocrate);
         let ro_path = path.path().join("ro-crate-metadata.json");
 
-        let zipped = zip_crate(&ro_path, true, 0, false, true);
+        let zipped = zip_crate(&ro_path, true, 0, false, true, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
         println!("{:?}", zipped);
         assert!(zipped.is_ok())
     }
@@ -1079,4 +2744,525 @@ mod write_crate_tests {
             assert_eq!(test, value);
         }
     }
+
+    #[test]
+    fn test_match_list_last_matching_rule_wins() {
+        let mut match_list = MatchList::new(true);
+        match_list.push("**/target/**", MatchType::Exclude).unwrap();
+        match_list
+            .push("target/keep-me.txt", MatchType::Include)
+            .unwrap();
+
+        assert!(!match_list.is_included(Path::new("target/debug/binary")));
+        assert!(match_list.is_included(Path::new("target/keep-me.txt")));
+        assert!(match_list.is_included(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_match_list_default_exclude() {
+        let mut match_list = MatchList::new(false);
+        match_list.push("data/**", MatchType::Include).unwrap();
+
+        assert!(match_list.is_included(Path::new("data/file.csv")));
+        assert!(!match_list.is_included(Path::new("cache/file.tmp")));
+    }
+
+    #[test]
+    fn test_tar_archive_writer_roundtrip() {
+        let mut buffer = Vec::new();
+        {
+            let mut archive = TarArchiveWriter::new(&mut buffer);
+            archive.start_entry("hello.txt", 0o644).unwrap();
+            archive.write_all(b"hello world").unwrap();
+            archive.add_directory("subdir").unwrap();
+            archive.finish().unwrap();
+        }
+
+        let mut reader = tar::Archive::new(buffer.as_slice());
+        let mut entries: Vec<(String, u64)> = reader
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (
+                    entry.path().unwrap().to_string_lossy().to_string(),
+                    entry.header().size().unwrap(),
+                )
+            })
+            .collect();
+        entries.sort();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("hello.txt".to_string(), 11),
+                ("subdir".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zip_crate_tar_format() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let tar_path = format!("{}/{}.tar", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Tar,
+            false,
+            CompressionOptions::default(),
+            false,
+        ChecksumOptions::default(),
+        false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+        assert!(Path::new(&tar_path).exists());
+    }
+
+    #[test]
+    fn test_zip_crate_create_entities_adds_undescribed_file() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        fs::write(path.path().join("undescribed.txt"), b"extra data").unwrap();
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            true,
+            CompressionOptions::default(),
+            false,
+        ChecksumOptions::default(),
+        false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+        assert!(parse_zip(&zip_path, 0).is_ok());
+
+        let dest = tempfile::tempdir().unwrap();
+        let extracted = unzip_crate(Path::new(&zip_path), dest.path(), false).unwrap();
+        let rewritten = read_crate(&extracted, 0).unwrap();
+        let entity = rewritten.get_entity("undescribed.txt");
+        assert!(entity.is_some());
+
+        let root = rewritten
+            .graph
+            .iter()
+            .find_map(|entity| match entity {
+                GraphVector::RootDataEntity(root) => Some(root),
+                _ => None,
+            })
+            .expect("expected root data entity");
+
+        let has_part = match root.dynamic_entity_immut() {
+            Some(fields) => match fields.get("hasPart") {
+                Some(DynamicEntity::EntityString(value)) => value.clone(),
+                _ => String::new(),
+            },
+            None => String::new(),
+        };
+        assert!(has_part.split(',').any(|id| id == "undescribed.txt"));
+    }
+
+    #[test]
+    fn test_list_archive_entries() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(&ro_path, false, 0, false, false, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+
+        let entries = list_archive_entries(Path::new(&zip_path)).unwrap();
+        assert!(entries
+            .iter()
+            .any(|entry| entry.name == "ro-crate-metadata.json"));
+        assert!(entries.iter().all(|entry| entry.size > 0 || entry.name == "ro-crate-metadata.json"));
+    }
+
+    #[test]
+    fn test_unzip_crate_roundtrip() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(&ro_path, false, 0, false, false, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+
+        let dest = tempfile::tempdir().unwrap();
+        let extracted = unzip_crate(Path::new(&zip_path), dest.path(), false).unwrap();
+
+        assert_eq!(extracted, dest.path().join("ro-crate-metadata.json"));
+        assert!(extracted.exists());
+        assert!(dest.path().join("data.csv").exists());
+    }
+
+    #[test]
+    fn test_unzip_crate_restores_external_entities() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(&ro_path, true, 0, false, false, None, false, None, ArchiveFormat::Zip, false, CompressionOptions::default(), false, ChecksumOptions::default(), false, 0);
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+
+        let dest = tempfile::tempdir().unwrap();
+        let extracted = unzip_crate(Path::new(&zip_path), dest.path(), true).unwrap();
+
+        let rocrate = read_crate(&extracted, 0).unwrap();
+        assert!(rocrate
+            .get_all_ids()
+            .iter()
+            .all(|id| !id.starts_with("external/")));
+        assert!(!dest.path().join("external").exists());
+    }
+
+    #[test]
+    fn test_zip_crate_stored_compression() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let compression = CompressionOptions {
+            method: CompressionMethod::Stored,
+            level: None,
+            preserve_permissions: false,
+        };
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            compression,
+            false,
+        ChecksumOptions::default(),
+        false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+        assert!(parse_zip(&zip_path, 0).is_ok());
+    }
+
+    #[test]
+    fn test_directory_walk_follow_symlinks_avoids_cycle() {
+        let workdir = tempfile::tempdir().unwrap();
+        let crate_dir = workdir.path().join("test_experiment");
+        fs::create_dir_all(&crate_dir).unwrap();
+
+        let ro_path = crate_dir.join("ro-crate-metadata.json");
+        let json = minimal_test_experiment_rocrate(crate_dir.clone());
+        let mut file = std::fs::File::create_new(&ro_path).unwrap();
+        file.write_all(json.to_string().as_bytes()).unwrap();
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&crate_dir, crate_dir.join("self_loop")).unwrap();
+        }
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            CompressionOptions::default(),
+            true,
+            ChecksumOptions::default(),
+            false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+    }
+
+    #[test]
+    fn test_zip_crate_checksums_manifest_verifies_clean() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            CompressionOptions::default(),
+            false,
+            ChecksumOptions {
+                enabled: true,
+                sha512: false,
+                blake3: false,
+            },
+            false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+
+        let mismatches = verify_archive_checksums(Path::new(&zip_path)).unwrap();
+        assert_eq!(mismatches, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_verify_archive_checksums_none_without_manifest() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let base = path.path().file_name().unwrap().to_string_lossy();
+        let zip_path = format!("{}/{}.zip", path.path().to_string_lossy(), base);
+
+        let zipped = zip_crate(
+            &ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            CompressionOptions::default(),
+            false,
+            ChecksumOptions::default(),
+            false,
+            0,
+        );
+        println!("{:?}", zipped);
+        assert!(zipped.is_ok());
+
+        let mismatches = verify_archive_checksums(Path::new(&zip_path)).unwrap();
+        assert_eq!(mismatches, None);
+    }
+
+    #[test]
+    fn test_zip_crate_checksums_deterministic_across_thread_counts() {
+        let single = create_tempcrate(&minimal_test_experiment_rocrate);
+        let single_ro_path = single.path().join("ro-crate-metadata.json");
+        let single_base = single.path().file_name().unwrap().to_string_lossy();
+        let single_zip_path = format!(
+            "{}/{}.zip",
+            single.path().to_string_lossy(),
+            single_base
+        );
+
+        let pooled = create_tempcrate(&minimal_test_experiment_rocrate);
+        let pooled_ro_path = pooled.path().join("ro-crate-metadata.json");
+        let pooled_base = pooled.path().file_name().unwrap().to_string_lossy();
+        let pooled_zip_path = format!(
+            "{}/{}.zip",
+            pooled.path().to_string_lossy(),
+            pooled_base
+        );
+
+        let checksums = ChecksumOptions {
+            enabled: true,
+            sha512: false,
+            blake3: false,
+        };
+
+        let zipped_single = zip_crate(
+            &single_ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            CompressionOptions::default(),
+            false,
+            checksums,
+            false,
+            0,
+        );
+        assert!(zipped_single.is_ok());
+
+        let zipped_pooled = zip_crate(
+            &pooled_ro_path,
+            false,
+            0,
+            false,
+            false,
+            None,
+            false,
+            None,
+            ArchiveFormat::Zip,
+            false,
+            CompressionOptions::default(),
+            false,
+            checksums,
+            false,
+            4,
+        );
+        assert!(zipped_pooled.is_ok());
+
+        let single_manifest = verify_archive_checksums(Path::new(&single_zip_path)).unwrap();
+        let pooled_manifest = verify_archive_checksums(Path::new(&pooled_zip_path)).unwrap();
+        assert_eq!(single_manifest, Some(Vec::new()));
+        assert_eq!(pooled_manifest, Some(Vec::new()));
+
+        let entry_names = |zip_path: &str| -> Vec<String> {
+            list_archive_entries(Path::new(zip_path))
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect()
+        };
+        assert_eq!(entry_names(&single_zip_path), entry_names(&pooled_zip_path));
+
+        let read_manifest = |zip_path: &str| -> String {
+            let mut zip_archive = zip::ZipArchive::new(File::open(zip_path).unwrap()).unwrap();
+            let mut manifest = String::new();
+            zip_archive
+                .by_name("manifest-sha256.txt")
+                .unwrap()
+                .read_to_string(&mut manifest)
+                .unwrap();
+            manifest
+        };
+        assert_eq!(read_manifest(&single_zip_path), read_manifest(&pooled_zip_path));
+    }
+
+    #[test]
+    fn test_classify_uri_distinguishes_schemes() {
+        assert!(matches!(
+            classify_uri("https://example.com/data.csv"),
+            UriKind::Http(id) if id == "https://example.com/data.csv"
+        ));
+        assert!(matches!(classify_uri("file:///tmp/foo.txt"), UriKind::File(_)));
+        assert!(matches!(classify_uri("not a url at all"), UriKind::Opaque(_)));
+    }
+
+    #[test]
+    fn test_classify_uri_decodes_base64_data_uri() {
+        let uri = "data:text/plain;base64,SGVsbG8=";
+        match classify_uri(uri) {
+            UriKind::Data { mime, bytes } => {
+                assert_eq!(mime.as_deref(), Some("text/plain"));
+                assert_eq!(bytes, b"Hello");
+            }
+            other => panic!("expected UriKind::Data, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_remote_file_name_prefers_url_segment() {
+        let name = remote_file_name("https://example.com/path/dataset.csv", None);
+        assert_eq!(name, "dataset.csv");
+    }
+
+    #[test]
+    fn test_remote_file_name_falls_back_to_content_type() {
+        let name = remote_file_name("https://example.com/download", Some("image/png"));
+        assert_eq!(name, "resource.png");
+    }
+
+    #[test]
+    fn test_serialization_format_from_path() {
+        assert_eq!(
+            SerializationFormat::from_path("ro-crate-metadata.yaml"),
+            SerializationFormat::Yaml
+        );
+        assert_eq!(
+            SerializationFormat::from_path("ro-crate-metadata.toml"),
+            SerializationFormat::Toml
+        );
+        assert_eq!(
+            SerializationFormat::from_path("ro-crate-metadata.json"),
+            SerializationFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_write_crate_as_yaml_and_toml() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let rocrate = read_crate(&ro_path, 0).unwrap();
+
+        let yaml_path = path.path().join("ro-crate-metadata.yaml");
+        write_crate_as(
+            &rocrate,
+            yaml_path.to_string_lossy().to_string(),
+            SerializationFormat::Yaml,
+        )
+        .unwrap();
+        let yaml_content = fs::read_to_string(&yaml_path).unwrap();
+        assert!(yaml_content.contains("@context"));
+
+        let toml_path = path.path().join("ro-crate-metadata.toml");
+        write_crate_as(
+            &rocrate,
+            toml_path.to_string_lossy().to_string(),
+            SerializationFormat::Toml,
+        )
+        .unwrap();
+        let toml_content = fs::read_to_string(&toml_path).unwrap();
+        assert!(toml_content.contains("graph"));
+    }
+
+    #[test]
+    fn test_to_ron_and_from_ron_round_trip() {
+        let path = create_tempcrate(&minimal_test_experiment_rocrate);
+        let ro_path = path.path().join("ro-crate-metadata.json");
+        let rocrate = read_crate(&ro_path, 0).unwrap();
+
+        let ron_path = path.path().join("ro-crate-metadata.ron");
+        to_ron(&rocrate, ron_path.to_string_lossy().to_string()).unwrap();
+
+        let ron_content = fs::read_to_string(&ron_path).unwrap();
+        assert!(ron_content.contains("context"));
+
+        let reloaded = from_ron(&ron_path).unwrap();
+        assert_eq!(reloaded.get_all_ids(), rocrate.get_all_ids());
+    }
 }